@@ -206,6 +206,9 @@ pub trait ProvideFormat<Context> {
 // as that is beyond the current scope. Suffice to say, as we go through later chapters,
 // it will become clearer on how having provider traits can impact us on thinking about how to
 // structure and implement modular code.
+//
+// Both provider traits are available as CGP components in cgp_examples::format,
+// together with the Provided wrapper that implements Serialize and Display through them.
 
 fn main() {
     test_format_string_provider();
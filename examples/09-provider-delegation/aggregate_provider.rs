@@ -42,6 +42,8 @@ where
 // We can then implement context-generic providers for the given provider traits,
 // such as to format and parse the context as JSON if the context implements Serialize and Deserialize:
 
+// Like the traits in shared.rs, these providers are local copies for this chapter,
+// implementing the hand written StringFormatter and StringParser traits.
 pub struct FormatAsJsonString;
 
 impl<Context> StringFormatter<Context> for FormatAsJsonString
@@ -12,6 +12,10 @@ pub trait HasComponents {
 use anyhow::Error;
 use serde::{Deserialize, Serialize};
 
+// These traits are written out by hand, together with their own HasComponents trait,
+// because this chapter builds provider delegation from scratch before the cgp macros
+// are introduced. They intentionally stay separate from the cgp based components in
+// cgp_examples::format, which is what other crates should use.
 pub trait CanFormatToString {
     fn format_to_string(&self) -> Result<String, Error>;
 }
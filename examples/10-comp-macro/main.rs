@@ -21,8 +21,14 @@
 // and look at how the macros can simplify the same code.
 //
 // Following is the full code after simplification using cgp:
-use anyhow::Error;
+//
+// Note, the components are spelled out here to show what cgp_component expands from.
+// Other crates should depend on the canonical definitions in cgp_examples::format instead.
+// Like those, the components below use the abstract error type of HasErrorType, and the
+// JSON providers raise serde_json::Error through CanRaiseError.
+use cgp::core::error::{ErrorRaiserComponent, ErrorTypeComponent};
 use cgp::prelude::*;
+use cgp_examples::error::{RaiseIntoAnyhow, UseAnyhowError};
 use serde::{Deserialize, Serialize};
 
 // Component definitions
@@ -31,8 +37,8 @@ use serde::{Deserialize, Serialize};
     provider: StringFormatter,
     context: Context,
     }]
-pub trait CanFormatToString {
-    fn format_to_string(&self) -> Result<String, Error>;
+pub trait CanFormatToString: HasErrorType {
+    fn format_to_string(&self) -> Result<String, Self::Error>;
 }
 
 #[cgp_component {
@@ -40,8 +46,8 @@ pub trait CanFormatToString {
     provider: StringParser,
     context: Context,
     }]
-pub trait CanParseFromString: Sized {
-    fn parse_from_string(raw: &str) -> Result<Self, Error>;
+pub trait CanParseFromString: Sized + HasErrorType {
+    fn parse_from_string(raw: &str) -> Result<Self, Self::Error>;
 }
 
 // Provider implementations
@@ -49,10 +55,10 @@ pub struct FormatAsJsonString;
 
 impl<Context> StringFormatter<Context> for FormatAsJsonString
 where
-    Context: Serialize,
+    Context: Serialize + CanRaiseError<serde_json::Error>,
 {
-    fn format_to_string(context: &Context) -> Result<String, Error> {
-        Ok(serde_json::to_string(context)?)
+    fn format_to_string(context: &Context) -> Result<String, Context::Error> {
+        serde_json::to_string(context).map_err(Context::raise_error)
     }
 }
 
@@ -60,10 +66,10 @@ pub struct ParseFromJsonString;
 
 impl<Context> StringParser<Context> for ParseFromJsonString
 where
-    Context: for<'a> Deserialize<'a>,
+    Context: for<'a> Deserialize<'a> + CanRaiseError<serde_json::Error>,
 {
-    fn parse_from_string(json_str: &str) -> Result<Context, Error> {
-        Ok(serde_json::from_str(json_str)?)
+    fn parse_from_string(json_str: &str) -> Result<Context, Context::Error> {
+        serde_json::from_str(json_str).map_err(Context::raise_error)
     }
 }
 
@@ -83,6 +89,8 @@ impl HasComponents for Person {
 
 delegate_components! {
     PersonComponents {
+        ErrorTypeComponent: UseAnyhowError,
+        ErrorRaiserComponent: RaiseIntoAnyhow,
         StringFormatterComponent: FormatAsJsonString,
         StringParserComponent: ParseFromJsonString,
     }
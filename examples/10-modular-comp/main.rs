@@ -1,11 +1,10 @@
-mod string_formatter_comp;
-mod string_parser_comp;
-
-use crate::string_formatter_comp::{
-    CanFormatToString, FormatAsJsonString, StringFormatterComponent,
-};
-use crate::string_parser_comp::{CanParseFromString, ParseFromJsonString, StringParserComponent};
+use cgp::core::error::{ErrorRaiserComponent, ErrorTypeComponent};
 use cgp::prelude::*;
+use cgp_examples::error::{RaiseIntoAnyhow, UseAnyhowError};
+use cgp_examples::format::{
+    CanFormatToString, CanParseFromString, FormatAsJsonString, ParseFromJsonString,
+    StringFormatterComponent, StringParserComponent,
+};
 use serde::{Deserialize, Serialize};
 
 // Concrete  type
@@ -27,13 +26,15 @@ impl HasComponents for Person {
 // Wire components to implementations
 delegate_components! {
     PersonComponents {
+        ErrorTypeComponent: UseAnyhowError,
+        ErrorRaiserComponent: RaiseIntoAnyhow,
         StringFormatterComponent: FormatAsJsonString,
         StringParserComponent: ParseFromJsonString,
     }
 }
 
-// Note, even though each component resides in a separate file,
-// in practice you may want to move larger components into a single crate.
+// Note, the components are defined once in the cgp_examples::format module of this crate,
+// so that every context, in this crate or elsewhere, wires up the same canonical definitions.
 
 fn main() {
     let person = Person {
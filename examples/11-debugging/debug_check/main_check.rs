@@ -1,11 +1,10 @@
-mod string_formatter_comp;
-mod string_parser_comp;
-
-use crate::string_formatter_comp::{
-    CanFormatToString, FormatAsJsonString, StringFormatterComponent,
-};
-use crate::string_parser_comp::{CanParseFromString, ParseFromJsonString, StringParserComponent};
+use cgp::core::error::{ErrorRaiserComponent, ErrorTypeComponent};
 use cgp::prelude::*;
+use cgp_examples::error::{RaiseIntoAnyhow, UseAnyhowError};
+use cgp_examples::format::{
+    CanFormatToString, CanParseFromString, FormatAsJsonString, ParseFromJsonString,
+    StringFormatterComponent, StringParserComponent,
+};
 use serde::{Deserialize, Serialize};
 use std::fmt::Debug;
 
@@ -43,6 +42,8 @@ impl HasComponents for Person {
 // Wire components to implementations
 delegate_components! {
     PersonComponents {
+        ErrorTypeComponent: UseAnyhowError,
+        ErrorRaiserComponent: RaiseIntoAnyhow,
         StringFormatterComponent: FormatAsJsonString,
         StringParserComponent: ParseFromJsonString,
     }
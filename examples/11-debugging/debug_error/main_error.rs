@@ -11,14 +11,13 @@
 // In other words, the impl-side provider constraints are enforced lazily in CGP,
 // and compile-time errors would only arise when we try to use a consumer trait against a concrete context.
 
-mod string_formatter_comp;
-mod string_parser_comp;
-
-use crate::string_formatter_comp::{
-    CanFormatToString, FormatAsJsonString, StringFormatterComponent,
-};
-use crate::string_parser_comp::{CanParseFromString, ParseFromJsonString, StringParserComponent};
+use cgp::core::error::{ErrorRaiserComponent, ErrorTypeComponent};
 use cgp::prelude::*;
+use cgp_examples::error::{RaiseIntoAnyhow, UseAnyhowError};
+use cgp_examples::format::{
+    CanFormatToString, CanParseFromString, FormatAsJsonString, ParseFromJsonString,
+    StringFormatterComponent, StringParserComponent,
+};
 use serde::{Deserialize, Serialize};
 // Unsatisfied Dependency Errors
 //
@@ -41,6 +40,8 @@ impl HasComponents for Person {
 
 delegate_components! {
     PersonComponents {
+        ErrorTypeComponent: UseAnyhowError,
+        ErrorRaiserComponent: RaiseIntoAnyhow,
         StringFormatterComponent: FormatAsJsonString,
         StringParserComponent: ParseFromJsonString,
     }
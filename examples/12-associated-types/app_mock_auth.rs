@@ -63,7 +63,7 @@ pub mod impls {
 
             let token_expiry = context.fetch_auth_token_expiry(auth_token)?;
 
-            if token_expiry < now {
+            if now < token_expiry {
                 Ok(())
             } else {
                 Err(anyhow!("auth token has expired"))
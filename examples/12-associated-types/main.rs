@@ -66,7 +66,7 @@ pub mod impls {
 
             let token_expiry = context.fetch_auth_token_expiry(auth_token)?;
 
-            if token_expiry < now {
+            if now < token_expiry {
                 Ok(())
             } else {
                 Err(anyhow!("auth token has expired"))
@@ -89,8 +89,8 @@ pub mod impls {
 }
 
 // We then define a context-generic provider ValidateTokenIsNotExpired, which validates auth tokens
-// by fetching the token's expiry time and the current time, and ensure that the token's expiry time
-// does not exceed the current time. We also define a context-generic provider GetSystemTimestamp,
+// by fetching the token's expiry time and the current time, and ensure that the current time
+// has not yet reached the token's expiry time. We also define a context-generic provider GetSystemTimestamp,
 // which gets the current time using std::time::System::now().
 
 pub mod contexts {
@@ -243,7 +243,7 @@ where
 
         let token_expiry = context.fetch_auth_token_expiry(auth_token)?;
 
-        if token_expiry < now {
+        if now < token_expiry {
             Ok(())
         } else {
             Err(anyhow!("auth token has expired"))
@@ -339,7 +339,7 @@ pub trait AppTrait {
 //
 //         let token_expiry = context.fetch_auth_token_expiry(auth_token)?;
 //
-//         if token_expiry < now {
+//         if now < token_expiry {
 //             Ok(())
 //         } else {
 //             Err(anyhow!("auth token has expired"))
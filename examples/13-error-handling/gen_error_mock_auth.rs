@@ -69,7 +69,7 @@ pub mod impls {
 
             let token_expiry = context.fetch_auth_token_expiry(auth_token)?;
 
-            if token_expiry < now {
+            if now < token_expiry {
                 Ok(())
             } else {
                 Err(Context::raise_error(ErrAuthTokenHasExpired))
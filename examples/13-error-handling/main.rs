@@ -124,7 +124,7 @@ where
 
         let token_expiry = context.fetch_auth_token_expiry(auth_token)?;
 
-        if token_expiry < now {
+        if now < token_expiry {
             Ok(())
         } else {
             Err("auth token has expired".into())
@@ -164,7 +164,7 @@ impl Display for ErrAuthTokenHasExpired {
 //
 //         let token_expiry = context.fetch_auth_token_expiry(auth_token)?;
 //
-//         if token_expiry < now {
+//         if now < token_expiry {
 //             Ok(())
 //         } else {
 //             // Replace the string error with a custom ErrAuthTokenHasExpired
@@ -236,7 +236,7 @@ impl Display for ErrAuthTokenHasExpired {
 //
 //         let token_expiry = context.fetch_auth_token_expiry(auth_token)?;
 //
-//         if token_expiry < now {
+//         if now < token_expiry {
 //             Ok(())
 //         } else {
 //             Err(Context::raise_error("auth token has expired"))
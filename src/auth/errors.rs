@@ -0,0 +1,129 @@
+use core::fmt::{self, Debug, Display};
+
+use cgp::core::error::{ErrorRaiser, ProvideErrorType};
+use cgp::prelude::*;
+
+use super::traits::HasTimeType;
+
+// Source errors raised by the auth providers. Each one carries just enough
+// information for the context error to decide how to report it.
+
+#[derive(Debug)]
+pub struct ErrUnknownAuthToken;
+
+impl Display for ErrUnknownAuthToken {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "unknown auth token")
+    }
+}
+
+impl core::error::Error for ErrUnknownAuthToken {}
+
+#[derive(Debug)]
+pub struct ErrAuthTokenHasExpired<Time> {
+    pub expiry: Time,
+    pub now: Time,
+}
+
+impl<Time: Debug> Display for ErrAuthTokenHasExpired<Time> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "auth token has expired at {:?}, current time is {:?}",
+            self.expiry, self.now
+        )
+    }
+}
+
+impl<Time: Debug> core::error::Error for ErrAuthTokenHasExpired<Time> {}
+
+#[derive(Debug)]
+pub struct ErrClockUnavailable;
+
+impl Display for ErrClockUnavailable {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "current time is unavailable")
+    }
+}
+
+impl core::error::Error for ErrClockUnavailable {}
+
+// Concrete context error for the auth components, so that callers can match on the reason
+// a token was rejected instead of parsing an error message.
+#[derive(Debug)]
+pub enum AuthError<Time> {
+    UnknownAuthToken(ErrUnknownAuthToken),
+    AuthTokenHasExpired(ErrAuthTokenHasExpired<Time>),
+    ClockUnavailable(ErrClockUnavailable),
+}
+
+impl<Time: Debug> Display for AuthError<Time> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnknownAuthToken(e) => Display::fmt(e, f),
+            Self::AuthTokenHasExpired(e) => Display::fmt(e, f),
+            Self::ClockUnavailable(e) => Display::fmt(e, f),
+        }
+    }
+}
+
+impl<Time: Debug> core::error::Error for AuthError<Time> {}
+
+impl<Time> From<ErrUnknownAuthToken> for AuthError<Time> {
+    fn from(e: ErrUnknownAuthToken) -> Self {
+        Self::UnknownAuthToken(e)
+    }
+}
+
+impl<Time> From<ErrAuthTokenHasExpired<Time>> for AuthError<Time> {
+    fn from(e: ErrAuthTokenHasExpired<Time>) -> Self {
+        Self::AuthTokenHasExpired(e)
+    }
+}
+
+impl<Time> From<ErrClockUnavailable> for AuthError<Time> {
+    fn from(e: ErrClockUnavailable) -> Self {
+        Self::ClockUnavailable(e)
+    }
+}
+
+// Uses AuthError over the context's time type as the context error.
+pub struct UseAuthError;
+
+impl<Context> ProvideErrorType<Context> for UseAuthError
+where
+    Context: HasTimeType,
+    Context::Time: Async + Debug,
+{
+    type Error = AuthError<Context::Time>;
+}
+
+// Raises each auth source error into its matching AuthError variant.
+pub struct RaiseAuthError;
+
+impl<Context> ErrorRaiser<Context, ErrUnknownAuthToken> for RaiseAuthError
+where
+    Context: HasTimeType + HasErrorType<Error = AuthError<Context::Time>>,
+{
+    fn raise_error(e: ErrUnknownAuthToken) -> AuthError<Context::Time> {
+        AuthError::UnknownAuthToken(e)
+    }
+}
+
+impl<Context> ErrorRaiser<Context, ErrAuthTokenHasExpired<Context::Time>> for RaiseAuthError
+where
+    Context: HasTimeType + HasErrorType<Error = AuthError<Context::Time>>,
+{
+    fn raise_error(e: ErrAuthTokenHasExpired<Context::Time>) -> AuthError<Context::Time> {
+        AuthError::AuthTokenHasExpired(e)
+    }
+}
+
+impl<Context> ErrorRaiser<Context, ErrClockUnavailable> for RaiseAuthError
+where
+    Context: HasTimeType + HasErrorType<Error = AuthError<Context::Time>>,
+{
+    fn raise_error(e: ErrClockUnavailable) -> AuthError<Context::Time> {
+        AuthError::ClockUnavailable(e)
+    }
+}
@@ -0,0 +1,102 @@
+use std::time::{Instant, SystemTime, UNIX_EPOCH};
+
+use cgp::prelude::*;
+use datetime::LocalDateTime;
+
+use super::errors::*;
+use super::traits::*;
+use crate::error::CanWrapError;
+
+pub struct ValidateTokenIsNotExpired;
+
+impl<Context> AuthTokenValidator<Context> for ValidateTokenIsNotExpired
+where
+    Context: HasCurrentTime
+        + CanFetchAuthTokenExpiry
+        + CanRaiseError<ErrAuthTokenHasExpired<Context::Time>>
+        + CanWrapError<&'static str>,
+    Context::Time: Ord,
+{
+    fn validate_auth_token(
+        context: &Context,
+        auth_token: &Context::AuthToken,
+    ) -> Result<(), Context::Error> {
+        let now = context
+            .current_time()
+            .map_err(|e| Context::wrap_error(e, "while getting the current time"))?;
+
+        let token_expiry = context
+            .fetch_auth_token_expiry(auth_token)
+            .map_err(|e| Context::wrap_error(e, "while fetching expiry for auth token"))?;
+
+        if now < token_expiry {
+            Ok(())
+        } else {
+            Err(Context::raise_error(ErrAuthTokenHasExpired {
+                expiry: token_expiry,
+                now,
+            }))
+        }
+    }
+}
+
+// Uses datetime::LocalDateTime as the time type, and reads the current local time.
+pub struct UseLocalDateTime;
+
+impl<Context> ProvideTimeType<Context> for UseLocalDateTime {
+    type Time = LocalDateTime;
+}
+
+impl<Context> CurrentTimeGetter<Context> for UseLocalDateTime
+where
+    Context: HasTimeType<Time = LocalDateTime> + HasErrorType,
+{
+    fn current_time(_context: &Context) -> Result<LocalDateTime, Context::Error> {
+        Ok(LocalDateTime::now())
+    }
+}
+
+// Uses std::time::Instant as the time type, and reads the current instant.
+pub struct UseInstant;
+
+impl<Context> ProvideTimeType<Context> for UseInstant {
+    type Time = Instant;
+}
+
+impl<Context> CurrentTimeGetter<Context> for UseInstant
+where
+    Context: HasTimeType<Time = Instant> + HasErrorType,
+{
+    fn current_time(_context: &Context) -> Result<Instant, Context::Error> {
+        Ok(Instant::now())
+    }
+}
+
+// Uses the unix timestamp in milliseconds as the time type, and reads it from the system clock.
+pub struct GetSystemTimestamp;
+
+impl<Context> ProvideTimeType<Context> for GetSystemTimestamp {
+    type Time = u64;
+}
+
+impl<Context> CurrentTimeGetter<Context> for GetSystemTimestamp
+where
+    Context: HasTimeType<Time = u64> + CanRaiseError<ErrClockUnavailable>,
+{
+    fn current_time(_context: &Context) -> Result<u64, Context::Error> {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_err(|_| Context::raise_error(ErrClockUnavailable))?
+            .as_millis()
+            .try_into()
+            .map_err(|_| Context::raise_error(ErrClockUnavailable))?;
+
+        Ok(now)
+    }
+}
+
+pub struct UseStringAuthToken;
+
+impl<Context> ProvideAuthTokenType<Context> for UseStringAuthToken {
+    type AuthToken = String;
+}
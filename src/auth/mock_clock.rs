@@ -0,0 +1,94 @@
+use core::marker::PhantomData;
+use core::ops::Add;
+use std::sync::{Mutex, MutexGuard, PoisonError};
+
+use cgp::core::field::impls::use_field::UseField;
+use cgp::prelude::*;
+
+use super::traits::*;
+
+// A controllable clock for tests, so that time dependent providers such as
+// ValidateTokenIsNotExpired can be exercised without reading the wall clock or sleeping.
+//
+// The clock only moves when told to. While frozen, advance() is ignored,
+// which allows a test to pin the time while the code under test tries to move it.
+// An explicit set() always takes effect.
+pub struct MockClock<Time> {
+    state: Mutex<MockClockState<Time>>,
+}
+
+struct MockClockState<Time> {
+    now: Time,
+    frozen: bool,
+}
+
+impl<Time> MockClock<Time> {
+    pub fn new(now: Time) -> Self {
+        Self {
+            state: Mutex::new(MockClockState { now, frozen: false }),
+        }
+    }
+
+    pub fn now(&self) -> Time
+    where
+        Time: Clone,
+    {
+        self.lock().now.clone()
+    }
+
+    pub fn set(&self, now: Time) {
+        self.lock().now = now;
+    }
+
+    pub fn advance<Duration>(&self, duration: Duration)
+    where
+        Time: Clone + Add<Duration, Output = Time>,
+    {
+        let mut state = self.lock();
+
+        if !state.frozen {
+            state.now = state.now.clone() + duration;
+        }
+    }
+
+    pub fn freeze(&self) {
+        self.lock().frozen = true;
+    }
+
+    pub fn unfreeze(&self) {
+        self.lock().frozen = false;
+    }
+
+    pub fn is_frozen(&self) -> bool {
+        self.lock().frozen
+    }
+
+    // A test that panicked while holding the lock leaves the clock state intact,
+    // so there is no reason to propagate the poison.
+    fn lock(&self) -> MutexGuard<'_, MockClockState<Time>> {
+        self.state.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+// Reads the current time from the context's MockClock.
+pub struct UseMockClock;
+
+impl<Context> CurrentTimeGetter<Context> for UseMockClock
+where
+    Context: HasMockClock + HasErrorType,
+    Context::Time: Clone,
+{
+    fn current_time(context: &Context) -> Result<Context::Time, Context::Error> {
+        Ok(context.mock_clock().now())
+    }
+}
+
+// Gets the MockClock from a context field, e.g. UseField<symbol!("clock")>.
+impl<Context, Tag> MockClockGetter<Context> for UseField<Tag>
+where
+    Context: HasTimeType + HasField<Tag, Value = MockClock<Context::Time>>,
+{
+    fn mock_clock(context: &Context) -> &MockClock<Context::Time> {
+        context.get_field(PhantomData)
+    }
+}
@@ -0,0 +1,14 @@
+// Auth token validation components from the associated types and error handling chapters,
+// generic over the abstract time, auth token and error types of a context.
+//
+// See examples/12-associated-types and examples/13-error-handling for the walkthrough.
+
+pub mod errors;
+pub mod impls;
+pub mod mock_clock;
+pub mod traits;
+
+pub use errors::*;
+pub use impls::*;
+pub use mock_clock::*;
+pub use traits::*;
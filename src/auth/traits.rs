@@ -0,0 +1,50 @@
+use cgp::prelude::*;
+
+use super::mock_clock::MockClock;
+
+#[cgp_component {
+    name: TimeTypeComponent,
+    provider: ProvideTimeType,
+    }]
+pub trait HasTimeType {
+    type Time;
+}
+
+#[cgp_component {
+    name: AuthTokenTypeComponent,
+    provider: ProvideAuthTokenType,
+    }]
+pub trait HasAuthTokenType {
+    type AuthToken;
+}
+
+#[cgp_component {
+    provider: AuthTokenValidator,
+    }]
+pub trait CanValidateAuthToken: HasAuthTokenType + HasErrorType {
+    fn validate_auth_token(&self, auth_token: &Self::AuthToken) -> Result<(), Self::Error>;
+}
+
+#[cgp_component {
+    provider: AuthTokenExpiryFetcher,
+    }]
+pub trait CanFetchAuthTokenExpiry: HasAuthTokenType + HasTimeType + HasErrorType {
+    fn fetch_auth_token_expiry(
+        &self,
+        auth_token: &Self::AuthToken,
+    ) -> Result<Self::Time, Self::Error>;
+}
+
+#[cgp_component {
+    provider: CurrentTimeGetter,
+    }]
+pub trait HasCurrentTime: HasTimeType + HasErrorType {
+    fn current_time(&self) -> Result<Self::Time, Self::Error>;
+}
+
+#[cgp_component {
+    provider: MockClockGetter,
+    }]
+pub trait HasMockClock: HasTimeType {
+    fn mock_clock(&self) -> &MockClock<Self::Time>;
+}
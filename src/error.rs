@@ -0,0 +1,172 @@
+// Context-generic error type and error raiser providers from the error handling chapter.
+//
+// See examples/13-error-handling for the walkthrough.
+
+use core::fmt::{self, Debug, Display};
+use core::marker::PhantomData;
+
+use anyhow::anyhow;
+use cgp::core::error::{ErrorRaiser, ProvideErrorType};
+use cgp::prelude::*;
+
+pub struct UseAnyhowError;
+
+impl<Context> ProvideErrorType<Context> for UseAnyhowError {
+    type Error = anyhow::Error;
+}
+
+pub struct RaiseFrom;
+
+impl<Context, SourceError> ErrorRaiser<Context, SourceError> for RaiseFrom
+where
+    Context: HasErrorType,
+    Context::Error: From<SourceError>,
+{
+    fn raise_error(e: SourceError) -> Context::Error {
+        e.into()
+    }
+}
+
+pub struct RaiseIntoAnyhow;
+
+impl<Context, SourceError> ErrorRaiser<Context, SourceError> for RaiseIntoAnyhow
+where
+    Context: HasErrorType<Error = anyhow::Error>,
+    SourceError: core::error::Error + Send + Sync + 'static,
+{
+    fn raise_error(e: SourceError) -> anyhow::Error {
+        e.into()
+    }
+}
+
+pub struct DebugAsAnyhow;
+
+impl<Context, SourceError> ErrorRaiser<Context, SourceError> for DebugAsAnyhow
+where
+    Context: HasErrorType<Error = anyhow::Error>,
+    SourceError: Debug,
+{
+    fn raise_error(e: SourceError) -> anyhow::Error {
+        anyhow!("{e:?}")
+    }
+}
+
+// Dispatches to a different error raiser for each source error type, so that one context
+// can mix raising strategies. The dispatch table is wired with delegate_components!,
+// using the source error types as keys:
+//
+// delegate_components! {
+//     MockAppErrorRaisers {
+//         ErrAuthTokenHasExpired<u64>: RaiseFrom,
+//         serde_json::Error: RaiseIntoAnyhow,
+//         [
+//             ErrUnknownAuthToken,
+//             ErrClockUnavailable,
+//         ]: DebugAsAnyhow,
+//     }
+// }
+//
+// Rust has no way to express a catch-all entry, so every source error that the context
+// raises has to be listed. A missing entry shows up as an unsatisfied
+// DelegateComponent<SourceError> constraint at the call site.
+pub struct DispatchErrorRaiser<Components>(pub PhantomData<Components>);
+
+impl<Context, SourceError, Components> ErrorRaiser<Context, SourceError>
+    for DispatchErrorRaiser<Components>
+where
+    Context: HasErrorType,
+    Components: DelegateComponent<SourceError>,
+    Components::Delegate: ErrorRaiser<Context, SourceError>,
+{
+    fn raise_error(e: SourceError) -> Context::Error {
+        Components::Delegate::raise_error(e)
+    }
+}
+
+// Attaches a detail, such as which step failed, to an error that is being propagated.
+#[cgp_component {
+    provider: ErrorWrapper,
+    }]
+pub trait CanWrapError<Detail>: HasErrorType {
+    fn wrap_error(error: Self::Error, detail: Detail) -> Self::Error;
+}
+
+// Wraps anyhow errors with the detail as context, same as anyhow::Context::context.
+pub struct WrapWithAnyhowContext;
+
+impl<Context, Detail> ErrorWrapper<Context, Detail> for WrapWithAnyhowContext
+where
+    Context: HasErrorType<Error = anyhow::Error>,
+    Detail: Display + Send + Sync + 'static,
+{
+    fn wrap_error(error: anyhow::Error, detail: Detail) -> anyhow::Error {
+        error.context(detail)
+    }
+}
+
+// Error that keeps the chain of details attached to a source error,
+// with the innermost detail first.
+#[derive(Debug)]
+pub struct DetailedError<Source> {
+    pub source: Source,
+    pub details: Vec<String>,
+}
+
+impl<Source> From<Source> for DetailedError<Source> {
+    fn from(source: Source) -> Self {
+        Self {
+            source,
+            details: Vec::new(),
+        }
+    }
+}
+
+impl<Source: Display> Display for DetailedError<Source> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for detail in self.details.iter().rev() {
+            write!(f, "{detail}: ")?;
+        }
+
+        write!(f, "{}", self.source)
+    }
+}
+
+impl<Source: Debug + Display> core::error::Error for DetailedError<Source> {}
+
+// Raises a source error into the DetailedError source using From, with an empty detail chain.
+pub struct RaiseDetailedFrom;
+
+impl<Context, SourceError, Source> ErrorRaiser<Context, SourceError> for RaiseDetailedFrom
+where
+    Context: HasErrorType<Error = DetailedError<Source>>,
+    Source: From<SourceError>,
+{
+    fn raise_error(e: SourceError) -> DetailedError<Source> {
+        Source::from(e).into()
+    }
+}
+
+pub struct PushErrorDetail;
+
+impl<Context, Detail, Source> ErrorWrapper<Context, Detail> for PushErrorDetail
+where
+    Context: HasErrorType<Error = DetailedError<Source>>,
+    Detail: Display,
+{
+    fn wrap_error(mut error: DetailedError<Source>, detail: Detail) -> DetailedError<Source> {
+        error.details.push(detail.to_string());
+        error
+    }
+}
+
+// Drops the detail and returns the error unchanged.
+pub struct IgnoreErrorDetail;
+
+impl<Context, Detail> ErrorWrapper<Context, Detail> for IgnoreErrorDetail
+where
+    Context: HasErrorType,
+{
+    fn wrap_error(error: Context::Error, _detail: Detail) -> Context::Error {
+        error
+    }
+}
@@ -0,0 +1,28 @@
+use cgp::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use super::bytes_decoder::BytesDecoder;
+use super::bytes_encoder::BytesEncoder;
+
+// Bincode is not self-describing, so both sides must agree on the exact context type.
+pub struct EncodeAsBincode;
+
+impl<Context> BytesEncoder<Context> for EncodeAsBincode
+where
+    Context: Serialize + CanRaiseError<bincode::Error>,
+{
+    fn encode_to_bytes(context: &Context) -> Result<Vec<u8>, Context::Error> {
+        bincode::serialize(context).map_err(Context::raise_error)
+    }
+}
+
+pub struct DecodeFromBincode;
+
+impl<Context> BytesDecoder<Context> for DecodeFromBincode
+where
+    Context: for<'a> Deserialize<'a> + CanRaiseError<bincode::Error>,
+{
+    fn decode_from_bytes(bytes: &[u8]) -> Result<Context, Context::Error> {
+        bincode::deserialize(bytes).map_err(Context::raise_error)
+    }
+}
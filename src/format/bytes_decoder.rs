@@ -0,0 +1,31 @@
+use core::marker::PhantomData;
+use core::str::Utf8Error;
+
+use cgp::prelude::*;
+
+use super::string_parser::StringParser;
+
+// Binary counterpart of CanParseFromString.
+#[cgp_component {
+    name: BytesDecoderComponent,
+    provider: BytesDecoder,
+    context: Context,
+    }]
+pub trait CanDecodeFromBytes: Sized + HasErrorType {
+    fn decode_from_bytes(bytes: &[u8]) -> Result<Self, Self::Error>;
+}
+
+// Derives a bytes decoder from a string parser, by reading the bytes as UTF-8 text first.
+pub struct DecodeFromUtf8<Parser>(pub PhantomData<Parser>);
+
+impl<Context, Parser> BytesDecoder<Context> for DecodeFromUtf8<Parser>
+where
+    Context: CanRaiseError<Utf8Error>,
+    Parser: StringParser<Context>,
+{
+    fn decode_from_bytes(bytes: &[u8]) -> Result<Context, Context::Error> {
+        let raw = core::str::from_utf8(bytes).map_err(Context::raise_error)?;
+
+        Parser::parse_from_string(raw)
+    }
+}
@@ -0,0 +1,30 @@
+use core::marker::PhantomData;
+
+use cgp::prelude::*;
+
+use super::string_formatter::StringFormatter;
+
+// Binary counterpart of CanFormatToString, for formats that are not meant to be read as text.
+#[cgp_component {
+    name: BytesEncoderComponent,
+    provider: BytesEncoder,
+    context: Context,
+    }]
+pub trait CanEncodeToBytes: HasErrorType {
+    fn encode_to_bytes(&self) -> Result<Vec<u8>, Self::Error>;
+}
+
+// Derives a bytes encoder from a string formatter, by encoding its output as UTF-8.
+// This lets the bytes wrappers such as Compressed apply to text formats,
+// e.g. Compressed<EncodeAsUtf8<FormatAsJsonString>, Gzip>.
+pub struct EncodeAsUtf8<Formatter>(pub PhantomData<Formatter>);
+
+impl<Context, Formatter> BytesEncoder<Context> for EncodeAsUtf8<Formatter>
+where
+    Context: HasErrorType,
+    Formatter: StringFormatter<Context>,
+{
+    fn encode_to_bytes(context: &Context) -> Result<Vec<u8>, Context::Error> {
+        Ok(Formatter::format_to_string(context)?.into_bytes())
+    }
+}
@@ -0,0 +1,163 @@
+use core::fmt::{self, Display};
+
+use cgp::prelude::*;
+use serde::Serialize;
+use serde_json::Value;
+
+use super::string_formatter::StringFormatter;
+
+// Formats the context as JSON following the JSON Canonicalization Scheme of RFC 8785,
+// so that logically equal values always produce the same bytes, e.g. for hashing or signing.
+// Object keys are sorted by their UTF-16 code units, numbers are written the way
+// ECMAScript does, and strings only escape what JSON requires.
+pub struct FormatAsCanonicalJson;
+
+impl<Context> StringFormatter<Context> for FormatAsCanonicalJson
+where
+    Context: Serialize + CanRaiseError<serde_json::Error> + CanRaiseError<ErrNumberOutOfRange>,
+{
+    fn format_to_string(context: &Context) -> Result<String, Context::Error> {
+        let value = serde_json::to_value(context).map_err(Context::raise_error)?;
+
+        to_canonical_json(&value).map_err(Context::raise_error)
+    }
+}
+
+// Raised for an integer that a double cannot represent exactly, such as 2^53 + 1.
+// RFC 8785 only accepts I-JSON numbers, and rounding such an integer would give
+// different values the same canonical form, and so the same digest or signature.
+#[derive(Debug, Eq, PartialEq)]
+pub struct ErrNumberOutOfRange {
+    pub number: String,
+}
+
+impl Display for ErrNumberOutOfRange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "number {} cannot be represented exactly in canonical JSON",
+            self.number
+        )
+    }
+}
+
+impl core::error::Error for ErrNumberOutOfRange {}
+
+pub fn to_canonical_json(value: &Value) -> Result<String, ErrNumberOutOfRange> {
+    let mut output = String::new();
+    write_value(value, &mut output)?;
+    Ok(output)
+}
+
+fn write_value(value: &Value, output: &mut String) -> Result<(), ErrNumberOutOfRange> {
+    match value {
+        Value::Null => output.push_str("null"),
+        Value::Bool(value) => output.push_str(if *value { "true" } else { "false" }),
+        Value::Number(number) => {
+            // Every JSON number is an IEEE 754 double in RFC 8785, including large integers
+            let double = number.as_f64().unwrap_or_default();
+
+            let is_exact = match (number.as_u64(), number.as_i64()) {
+                (Some(integer), _) => double < 2f64.powi(64) && double as u64 == integer,
+                (None, Some(integer)) => double as i64 == integer,
+                (None, None) => true,
+            };
+
+            if !is_exact {
+                return Err(ErrNumberOutOfRange {
+                    number: number.to_string(),
+                });
+            }
+
+            write_number(double, output);
+        }
+        Value::String(value) => write_string(value, output),
+        Value::Array(items) => {
+            output.push('[');
+
+            for (index, item) in items.iter().enumerate() {
+                if index > 0 {
+                    output.push(',');
+                }
+                write_value(item, output)?;
+            }
+
+            output.push(']');
+        }
+        Value::Object(entries) => {
+            let mut entries: Vec<_> = entries.iter().collect();
+            entries.sort_by(|(a, _), (b, _)| a.encode_utf16().cmp(b.encode_utf16()));
+
+            output.push('{');
+
+            for (index, (key, entry)) in entries.into_iter().enumerate() {
+                if index > 0 {
+                    output.push(',');
+                }
+                write_string(key, output);
+                output.push(':');
+                write_value(entry, output)?;
+            }
+
+            output.push('}');
+        }
+    }
+
+    Ok(())
+}
+
+// serde_json already escapes strings the way RFC 8785 requires: only `"`, `\` and
+// control characters are escaped, using the short forms where they exist and
+// lowercase `\u00xx` otherwise.
+fn write_string(value: &str, output: &mut String) {
+    output.push_str(&Value::from(value).to_string());
+}
+
+// Writes a finite double the way ECMAScript's Number.prototype.toString does,
+// using the shortest digits that round trip, as produced by Rust's `{:e}`.
+fn write_number(number: f64, output: &mut String) {
+    if number == 0.0 || !number.is_finite() {
+        // -0 is written as 0, and serde_json never produces NaN or infinities
+        output.push('0');
+        return;
+    }
+
+    if number < 0.0 {
+        output.push('-');
+    }
+
+    let formatted = format!("{:e}", number.abs());
+    let (mantissa, exponent) = formatted.split_once('e').unwrap_or((&formatted, "0"));
+    let digits: String = mantissa.chars().filter(|c| *c != '.').collect();
+    let exponent: i32 = exponent.parse().unwrap_or_default();
+
+    // The position of the decimal point relative to the start of the digits
+    let point = exponent + 1;
+    let len = digits.len() as i32;
+
+    if len <= point && point <= 21 {
+        output.push_str(&digits);
+        output.extend(core::iter::repeat_n('0', (point - len) as usize));
+    } else if 0 < point && point <= 21 {
+        let (integer, fraction) = digits.split_at(point as usize);
+        output.push_str(integer);
+        output.push('.');
+        output.push_str(fraction);
+    } else if -6 < point && point <= 0 {
+        output.push_str("0.");
+        output.extend(core::iter::repeat_n('0', -point as usize));
+        output.push_str(&digits);
+    } else {
+        let (first, rest) = digits.split_at(1);
+        output.push_str(first);
+
+        if !rest.is_empty() {
+            output.push('.');
+            output.push_str(rest);
+        }
+
+        output.push('e');
+        output.push(if point > 0 { '+' } else { '-' });
+        output.push_str(&(point - 1).abs().to_string());
+    }
+}
@@ -0,0 +1,31 @@
+use cgp::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use super::bytes_decoder::BytesDecoder;
+use super::bytes_encoder::BytesEncoder;
+
+pub struct EncodeAsCbor;
+
+impl<Context> BytesEncoder<Context> for EncodeAsCbor
+where
+    Context: Serialize + CanRaiseError<ciborium::ser::Error<std::io::Error>>,
+{
+    fn encode_to_bytes(context: &Context) -> Result<Vec<u8>, Context::Error> {
+        let mut bytes = Vec::new();
+
+        ciborium::into_writer(context, &mut bytes).map_err(Context::raise_error)?;
+
+        Ok(bytes)
+    }
+}
+
+pub struct DecodeFromCbor;
+
+impl<Context> BytesDecoder<Context> for DecodeFromCbor
+where
+    Context: for<'a> Deserialize<'a> + CanRaiseError<ciborium::de::Error<std::io::Error>>,
+{
+    fn decode_from_bytes(bytes: &[u8]) -> Result<Context, Context::Error> {
+        ciborium::from_reader(bytes).map_err(Context::raise_error)
+    }
+}
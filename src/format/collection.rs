@@ -0,0 +1,22 @@
+use cgp::prelude::*;
+
+// Formats a list of contexts as a single document, such as the rows of a table.
+// The context is the type of a single record, e.g. Person::format_collection(&people).
+#[cgp_component {
+    name: CollectionFormatterComponent,
+    provider: CollectionFormatter,
+    context: Context,
+    }]
+pub trait CanFormatCollection: Sized + HasErrorType {
+    fn format_collection(items: &[Self]) -> Result<String, Self::Error>;
+}
+
+// Parses a document back into the list of contexts it holds.
+#[cgp_component {
+    name: CollectionParserComponent,
+    provider: CollectionParser,
+    context: Context,
+    }]
+pub trait CanParseCollection: Sized + HasErrorType {
+    fn parse_collection(raw: &str) -> Result<Vec<Self>, Self::Error>;
+}
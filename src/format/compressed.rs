@@ -0,0 +1,154 @@
+use core::fmt::{self, Display};
+use core::marker::PhantomData;
+use std::io::{self, Read};
+
+use cgp::prelude::*;
+
+use super::bytes_decoder::BytesDecoder;
+use super::bytes_encoder::BytesEncoder;
+
+// The size in bytes from which the encoded output of the context gets compressed.
+// Smaller payloads are left as is, since compressing them rarely pays off.
+#[cgp_component {
+    provider: CompressionThresholdGetter,
+    }]
+pub trait HasCompressionThreshold {
+    fn compression_threshold() -> usize;
+}
+
+// The maximum size in bytes that a compressed payload may decompress to.
+// Decompression stops as soon as the output goes over it, so that a small payload
+// cannot expand into an arbitrarily large buffer.
+#[cgp_component {
+    provider: MaxDecompressedSizeGetter,
+    }]
+pub trait HasMaxDecompressedSize {
+    fn max_decompressed_size() -> usize;
+}
+
+// Raised when a compressed payload decompresses to more than the maximum decompressed size.
+#[derive(Debug, Eq, PartialEq)]
+pub struct ErrDecompressedSizeExceeded {
+    pub max: usize,
+}
+
+impl Display for ErrDecompressedSizeExceeded {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "decompressed size exceeds the limit of {} bytes",
+            self.max
+        )
+    }
+}
+
+impl core::error::Error for ErrDecompressedSizeExceeded {}
+
+// A compression format, along with the magic bytes that every compressed payload starts with.
+pub trait CompressionAlgorithm {
+    const MAGIC: &'static [u8];
+
+    fn compress(bytes: &[u8]) -> io::Result<Vec<u8>>;
+
+    // Returns a reader over the decompressed bytes, which are only produced as they are read.
+    fn decompressor<'a>(bytes: &'a [u8]) -> io::Result<impl Read + 'a>;
+}
+
+// Compresses the output of the inner encoder once it reaches the compression threshold,
+// and decompresses input that starts with the magic bytes of the algorithm before
+// passing it to the inner decoder. Input without the magic bytes is decoded as is.
+// Text formats are compressed through EncodeAsUtf8 and DecodeFromUtf8, and can be turned
+// back into text with an encoding, e.g.
+// FormatAsEncodedString<Compressed<EncodeAsUtf8<FormatAsJsonString>, Gzip>, Base64>.
+pub struct Compressed<Inner, Algorithm>(pub PhantomData<(Inner, Algorithm)>);
+
+impl<Context, Inner, Algorithm> BytesEncoder<Context> for Compressed<Inner, Algorithm>
+where
+    Context: HasCompressionThreshold + CanRaiseError<io::Error>,
+    Inner: BytesEncoder<Context>,
+    Algorithm: CompressionAlgorithm,
+{
+    fn encode_to_bytes(context: &Context) -> Result<Vec<u8>, Context::Error> {
+        let bytes = Inner::encode_to_bytes(context)?;
+
+        if bytes.len() < Context::compression_threshold() {
+            Ok(bytes)
+        } else {
+            Algorithm::compress(&bytes).map_err(Context::raise_error)
+        }
+    }
+}
+
+impl<Context, Inner, Algorithm> BytesDecoder<Context> for Compressed<Inner, Algorithm>
+where
+    Context: HasMaxDecompressedSize
+        + CanRaiseError<io::Error>
+        + CanRaiseError<ErrDecompressedSizeExceeded>,
+    Inner: BytesDecoder<Context>,
+    Algorithm: CompressionAlgorithm,
+{
+    fn decode_from_bytes(bytes: &[u8]) -> Result<Context, Context::Error> {
+        if bytes.starts_with(Algorithm::MAGIC) {
+            let max = Context::max_decompressed_size();
+
+            // Reading one byte past the maximum tells a payload that fits exactly
+            // apart from one that goes over
+            let mut decompressed = Vec::new();
+            Algorithm::decompressor(bytes)
+                .and_then(|decompressor| {
+                    decompressor
+                        .take((max as u64).saturating_add(1))
+                        .read_to_end(&mut decompressed)
+                })
+                .map_err(Context::raise_error)?;
+
+            if decompressed.len() > max {
+                return Err(Context::raise_error(ErrDecompressedSizeExceeded { max }));
+            }
+
+            Inner::decode_from_bytes(&decompressed)
+        } else {
+            Inner::decode_from_bytes(bytes)
+        }
+    }
+}
+
+#[cfg(feature = "gzip")]
+pub struct Gzip;
+
+#[cfg(feature = "gzip")]
+impl CompressionAlgorithm for Gzip {
+    const MAGIC: &'static [u8] = &[0x1f, 0x8b];
+
+    fn compress(bytes: &[u8]) -> io::Result<Vec<u8>> {
+        use std::io::Write;
+
+        use flate2::write::GzEncoder;
+        use flate2::Compression;
+
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(bytes)?;
+        encoder.finish()
+    }
+
+    fn decompressor<'a>(bytes: &'a [u8]) -> io::Result<impl Read + 'a> {
+        Ok(flate2::read::GzDecoder::new(bytes))
+    }
+}
+
+#[cfg(feature = "zstd")]
+pub struct Zstd;
+
+#[cfg(feature = "zstd")]
+impl CompressionAlgorithm for Zstd {
+    const MAGIC: &'static [u8] = &[0x28, 0xb5, 0x2f, 0xfd];
+
+    fn compress(bytes: &[u8]) -> io::Result<Vec<u8>> {
+        // Level 0 picks zstd's default level
+        zstd::encode_all(bytes, 0)
+    }
+
+    fn decompressor<'a>(bytes: &'a [u8]) -> io::Result<impl Read + 'a> {
+        zstd::Decoder::with_buffer(bytes)
+    }
+}
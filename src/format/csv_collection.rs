@@ -0,0 +1,131 @@
+use core::fmt::{self, Display};
+use core::marker::PhantomData;
+
+use cgp::prelude::*;
+use csv::{ErrorKind, ReaderBuilder, WriterBuilder};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+use super::collection::{CollectionFormatter, CollectionParser};
+
+// The field delimiter of a delimiter-separated values format.
+pub trait Delimiter {
+    const DELIMITER: u8;
+}
+
+// Comma-separated values, as in RFC 4180.
+pub struct Csv;
+
+impl Delimiter for Csv {
+    const DELIMITER: u8 = b',';
+}
+
+// Tab-separated values.
+pub struct Tsv;
+
+impl Delimiter for Tsv {
+    const DELIMITER: u8 = b'\t';
+}
+
+// Raised when a row of the input cannot be parsed into the context.
+// The row is the 1-based line number, counting the header row, and the column
+// is the 1-based field number, when the error can be traced back to a single field.
+#[derive(Debug)]
+pub struct ErrInvalidRow {
+    pub row: u64,
+    pub column: Option<u64>,
+    pub message: String,
+}
+
+impl Display for ErrInvalidRow {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.column {
+            Some(column) => write!(
+                f,
+                "invalid value at row {}, column {}: {}",
+                self.row, column, self.message
+            ),
+            None => write!(f, "invalid row {}: {}", self.row, self.message),
+        }
+    }
+}
+
+impl core::error::Error for ErrInvalidRow {}
+
+// Writes the contexts as rows after a header row with their field names.
+// Fields that contain the delimiter, quotes or line breaks are quoted, with quotes doubled.
+// Since the header is taken from the first record, an empty list produces an empty document.
+pub struct FormatAsDelimited<Dialect>(pub PhantomData<Dialect>);
+
+pub type FormatAsCsv = FormatAsDelimited<Csv>;
+
+pub type FormatAsTsv = FormatAsDelimited<Tsv>;
+
+impl<Context, Dialect> CollectionFormatter<Context> for FormatAsDelimited<Dialect>
+where
+    Context: Serialize + CanRaiseError<csv::Error>,
+    Dialect: Delimiter,
+{
+    fn format_collection(items: &[Context]) -> Result<String, Context::Error> {
+        let mut writer = WriterBuilder::new()
+            .delimiter(Dialect::DELIMITER)
+            .from_writer(Vec::new());
+
+        for item in items {
+            writer.serialize(item).map_err(Context::raise_error)?;
+        }
+
+        let bytes = writer
+            .into_inner()
+            .map_err(|e| Context::raise_error(e.into_error().into()))?;
+
+        // Every field was written from a Rust string, so the output is always valid UTF-8
+        Ok(String::from_utf8_lossy(&bytes).into_owned())
+    }
+}
+
+// Parses rows into contexts, matching columns to fields by the names in the header row.
+pub struct ParseFromDelimited<Dialect>(pub PhantomData<Dialect>);
+
+pub type ParseFromCsv = ParseFromDelimited<Csv>;
+
+pub type ParseFromTsv = ParseFromDelimited<Tsv>;
+
+impl<Context, Dialect> CollectionParser<Context> for ParseFromDelimited<Dialect>
+where
+    Context: DeserializeOwned + CanRaiseError<ErrInvalidRow>,
+    Dialect: Delimiter,
+{
+    fn parse_collection(raw: &str) -> Result<Vec<Context>, Context::Error> {
+        ReaderBuilder::new()
+            .delimiter(Dialect::DELIMITER)
+            .from_reader(raw.as_bytes())
+            .deserialize()
+            .map(|row| row.map_err(|e| Context::raise_error(invalid_row(e))))
+            .collect()
+    }
+}
+
+fn invalid_row(error: csv::Error) -> ErrInvalidRow {
+    let row = error.position().map(|pos| pos.line()).unwrap_or_default();
+
+    let (column, message) = match error.kind() {
+        ErrorKind::Deserialize { err, .. } => {
+            (err.field().map(|field| field + 1), err.kind().to_string())
+        }
+        ErrorKind::Utf8 { err, .. } => (Some(err.field() as u64 + 1), err.to_string()),
+        ErrorKind::UnequalLengths {
+            expected_len, len, ..
+        } => (
+            None,
+            format!("expected {} fields, but found {}", expected_len, len),
+        ),
+        _ => (None, error.to_string()),
+    };
+
+    ErrInvalidRow {
+        row,
+        column,
+        message,
+    }
+}
@@ -0,0 +1,167 @@
+use core::fmt::{self, Display};
+
+// Raised by the string parsers when the input cannot be parsed, pointing at where it went wrong.
+// The line and column are 1-based, with the column counted in characters,
+// and the offset is the 0-based byte offset into the input.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ParseDiagnostic {
+    pub format: &'static str,
+    pub message: String,
+    pub line: usize,
+    pub column: usize,
+    pub offset: usize,
+    pub expected: Option<String>,
+    pub found: String,
+    pub snippet: String,
+}
+
+impl ParseDiagnostic {
+    // Builds the diagnostic from the byte offset reported by the parser,
+    // which is clamped to the input and moved back to the closest character boundary.
+    pub fn at_offset(
+        format: &'static str,
+        input: &str,
+        offset: usize,
+        message: impl Into<String>,
+    ) -> Self {
+        let mut offset = offset.min(input.len());
+        while !input.is_char_boundary(offset) {
+            offset -= 1;
+        }
+
+        let line_start = input[..offset].rfind('\n').map_or(0, |index| index + 1);
+        let line_end = input[offset..]
+            .find('\n')
+            .map_or(input.len(), |index| offset + index);
+
+        let line = input[..line_start].matches('\n').count() + 1;
+        let column = input[line_start..offset].chars().count() + 1;
+
+        let message = message.into();
+        let (expected, found) = describe_message(&message);
+        let found = found.unwrap_or_else(|| describe_token(&input[offset..]));
+
+        let source_line = input[line_start..line_end].trim_end_matches('\r');
+        let (source_line, caret_column) = snippet_window(source_line, column);
+        let gutter = " ".repeat(line.to_string().len());
+        let snippet = format!(
+            "{gutter} |\n{line} | {source_line}\n{gutter} | {}^",
+            " ".repeat(caret_column - 1)
+        );
+
+        Self {
+            format,
+            message,
+            line,
+            column,
+            offset,
+            expected,
+            found,
+            snippet,
+        }
+    }
+
+    // Builds the diagnostic from the 1-based line and column reported by the parser,
+    // where the column is counted in bytes from the start of the line.
+    pub fn at_line_column(
+        format: &'static str,
+        input: &str,
+        line: usize,
+        column: usize,
+        message: impl Into<String>,
+    ) -> Self {
+        let line_start = input
+            .split_inclusive('\n')
+            .take(line.saturating_sub(1))
+            .map(str::len)
+            .sum::<usize>();
+
+        Self::at_offset(
+            format,
+            input,
+            line_start + column.saturating_sub(1),
+            message,
+        )
+    }
+}
+
+impl Display for ParseDiagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "failed to parse {} at line {}, column {}: {}\n{}",
+            self.format, self.line, self.column, self.message, self.snippet
+        )
+    }
+}
+
+impl core::error::Error for ParseDiagnostic {}
+
+const SNIPPET_CONTEXT: usize = 40;
+
+// Cuts long lines down to the characters around the column, marking the cuts with `...`,
+// and returns the column of the caret within the shortened line.
+fn snippet_window(source_line: &str, column: usize) -> (String, usize) {
+    let start = column.saturating_sub(SNIPPET_CONTEXT + 1);
+    let len = source_line.chars().count();
+
+    let mut window: String = source_line
+        .chars()
+        .skip(start)
+        .take(SNIPPET_CONTEXT * 2)
+        .collect();
+    let mut caret_column = column - start;
+
+    if start > 0 {
+        window.insert_str(0, "...");
+        caret_column += 3;
+    }
+
+    if start + SNIPPET_CONTEXT * 2 < len {
+        window.push_str("...");
+    }
+
+    (window, caret_column)
+}
+
+// Splits serde's "invalid type: found, expected expected" messages into their two halves.
+fn describe_message(message: &str) -> (Option<String>, Option<String>) {
+    let (found, expected) = match message.split_once(", expected ") {
+        Some((found, expected)) => (Some(found), Some(expected)),
+        None => (None, message.split_once("expected ").map(|(_, e)| e)),
+    };
+
+    // Some parsers prefix the message with the path of the field, as in "port: invalid type: ..."
+    let found = found.map(|found| {
+        ["invalid type: ", "invalid value: ", "invalid length "]
+            .iter()
+            .find_map(|prefix| found.split_once(prefix).map(|(_, found)| found))
+            .unwrap_or(found)
+            .to_owned()
+    });
+
+    (expected.map(str::to_owned), found)
+}
+
+// Describes the token at the start of the rest of the input, for parsers that do not say
+// what they found.
+fn describe_token(rest: &str) -> String {
+    let token: String = match rest.chars().next() {
+        None => return "end of input".to_owned(),
+        Some(c) if c.is_alphanumeric() || c == '_' => rest
+            .chars()
+            .take_while(|c| c.is_alphanumeric() || matches!(c, '_' | '-' | '.'))
+            .collect(),
+        Some(c) => c.to_string(),
+    };
+
+    format!("`{}`", token.escape_debug())
+}
+
+// Removes the " at line 1 column 2" suffix that serde_json and serde_yaml append to their
+// messages, since the diagnostic reports the location on its own.
+pub(crate) fn strip_location(message: &str) -> &str {
+    message
+        .rfind(" at line ")
+        .map_or(message, |index| &message[..index])
+}
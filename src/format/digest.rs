@@ -0,0 +1,56 @@
+use core::fmt::{self, Display};
+use core::marker::PhantomData;
+
+use cgp::prelude::*;
+use sha2::{Digest, Sha256};
+
+use super::string_formatter::StringFormatter;
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub struct Sha256Digest(pub [u8; 32]);
+
+impl Sha256Digest {
+    pub fn as_bytes(&self) -> &[u8; 32] {
+        &self.0
+    }
+
+    // The digest as a quoted strong entity tag, for use in an ETag header.
+    pub fn to_etag(&self) -> String {
+        format!("\"{}\"", self)
+    }
+}
+
+// Writes the digest as lowercase hex.
+impl Display for Sha256Digest {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for byte in self.0.iter() {
+            write!(f, "{:02x}", byte)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cgp_component {
+    provider: DigestComputer,
+    }]
+pub trait CanComputeDigest: HasErrorType {
+    fn compute_digest(&self) -> Result<Sha256Digest, Self::Error>;
+}
+
+// Computes the SHA-256 digest over the output of a string formatter.
+// Use DigestWithSha256<FormatAsCanonicalJson> so that logically equal values
+// always have the same digest.
+pub struct DigestWithSha256<Formatter>(pub PhantomData<Formatter>);
+
+impl<Context, Formatter> DigestComputer<Context> for DigestWithSha256<Formatter>
+where
+    Context: HasErrorType,
+    Formatter: StringFormatter<Context>,
+{
+    fn compute_digest(context: &Context) -> Result<Sha256Digest, Context::Error> {
+        let output = Formatter::format_to_string(context)?;
+
+        Ok(Sha256Digest(Sha256::digest(output.as_bytes()).into()))
+    }
+}
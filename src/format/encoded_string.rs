@@ -0,0 +1,90 @@
+use core::marker::PhantomData;
+
+use cgp::prelude::*;
+
+use super::bytes_decoder::BytesDecoder;
+use super::bytes_encoder::BytesEncoder;
+use super::string_formatter::StringFormatter;
+use super::string_parser::StringParser;
+
+// Text encoding of raw bytes, such as base64 or hex.
+pub trait BytesToStringEncoding {
+    type DecodeError;
+
+    fn encode(bytes: &[u8]) -> String;
+
+    fn decode(encoded: &str) -> Result<Vec<u8>, Self::DecodeError>;
+}
+
+// Derives a string formatter from a bytes encoder, by encoding its output as text.
+// For example, FormatAsEncodedString<EncodeAsCbor, Base64>.
+pub struct FormatAsEncodedString<Encoder, Encoding>(pub PhantomData<(Encoder, Encoding)>);
+
+impl<Context, Encoder, Encoding> StringFormatter<Context>
+    for FormatAsEncodedString<Encoder, Encoding>
+where
+    Context: HasErrorType,
+    Encoder: BytesEncoder<Context>,
+    Encoding: BytesToStringEncoding,
+{
+    fn format_to_string(context: &Context) -> Result<String, Context::Error> {
+        let bytes = Encoder::encode_to_bytes(context)?;
+
+        Ok(Encoding::encode(&bytes))
+    }
+}
+
+// Derives a string parser from a bytes decoder, by decoding the text back into bytes first.
+// For example, ParseFromEncodedString<DecodeFromCbor, Base64>.
+pub struct ParseFromEncodedString<Decoder, Encoding>(pub PhantomData<(Decoder, Encoding)>);
+
+impl<Context, Decoder, Encoding> StringParser<Context> for ParseFromEncodedString<Decoder, Encoding>
+where
+    Context: CanRaiseError<Encoding::DecodeError>,
+    Decoder: BytesDecoder<Context>,
+    Encoding: BytesToStringEncoding,
+{
+    fn parse_from_string(raw: &str) -> Result<Context, Context::Error> {
+        let bytes = Encoding::decode(raw).map_err(Context::raise_error)?;
+
+        Decoder::decode_from_bytes(&bytes)
+    }
+}
+
+// Standard base64 alphabet with padding.
+#[cfg(feature = "base64")]
+pub struct Base64;
+
+#[cfg(feature = "base64")]
+impl BytesToStringEncoding for Base64 {
+    type DecodeError = base64::DecodeError;
+
+    fn encode(bytes: &[u8]) -> String {
+        use base64::Engine;
+
+        base64::engine::general_purpose::STANDARD.encode(bytes)
+    }
+
+    fn decode(encoded: &str) -> Result<Vec<u8>, base64::DecodeError> {
+        use base64::Engine;
+
+        base64::engine::general_purpose::STANDARD.decode(encoded)
+    }
+}
+
+// Lowercase hex.
+#[cfg(feature = "hex")]
+pub struct Hex;
+
+#[cfg(feature = "hex")]
+impl BytesToStringEncoding for Hex {
+    type DecodeError = hex::FromHexError;
+
+    fn encode(bytes: &[u8]) -> String {
+        hex::encode(bytes)
+    }
+
+    fn decode(encoded: &str) -> Result<Vec<u8>, hex::FromHexError> {
+        hex::decode(encoded)
+    }
+}
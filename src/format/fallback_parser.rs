@@ -0,0 +1,176 @@
+use core::any::type_name;
+use core::fmt::{self, Debug, Display};
+use core::marker::PhantomData;
+
+use cgp::core::error::ErrorOf;
+use cgp::prelude::*;
+
+use super::string_parser::{ParseFromJsonString, StringParser};
+
+#[derive(Debug)]
+pub struct ParserFailure<Error> {
+    pub parser: &'static str,
+    pub error: Error,
+}
+
+// Raised when none of the parsers of ParseWithFallback or ParseBySniffing
+// could parse the input, with the failure of each parser in the order they were tried.
+#[derive(Debug)]
+pub struct ErrAllParsersFailed<Error> {
+    pub failures: Vec<ParserFailure<Error>>,
+}
+
+impl<Error: Debug> Display for ErrAllParsersFailed<Error> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "all {} parsers failed:", self.failures.len())?;
+
+        for failure in self.failures.iter() {
+            write!(f, "\n  {}: {:?}", failure.parser, failure.error)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl<Error: Debug> core::error::Error for ErrAllParsersFailed<Error> {}
+
+// Tries each parser in a tuple in order, and returns the first success,
+// e.g. ParseWithFallback<(ParseFromJsonString, ParseFromYamlString, ParseFromTomlString)>.
+pub struct ParseWithFallback<Parsers>(pub PhantomData<Parsers>);
+
+impl<Context, Parsers> StringParser<Context> for ParseWithFallback<Parsers>
+where
+    Context: HasErrorType + CanRaiseError<ErrAllParsersFailed<ErrorOf<Context>>>,
+    Parsers: FallbackParsers<Context>,
+{
+    fn parse_from_string(raw: &str) -> Result<Context, Context::Error> {
+        let mut failures = Vec::new();
+
+        match Parsers::try_parse(raw, &mut failures) {
+            Some(context) => Ok(context),
+            None => Err(Context::raise_error(ErrAllParsersFailed { failures })),
+        }
+    }
+}
+
+// Same as ParseWithFallback, but first tries the parsers whose format the input looks like,
+// so that a JSON payload is not needlessly parsed as YAML first. The remaining parsers
+// are still tried afterwards, in case the sniffing guessed wrong.
+pub struct ParseBySniffing<Parsers>(pub PhantomData<Parsers>);
+
+impl<Context, Parsers> StringParser<Context> for ParseBySniffing<Parsers>
+where
+    Context: HasErrorType + CanRaiseError<ErrAllParsersFailed<ErrorOf<Context>>>,
+    Parsers: SniffingParsers<Context>,
+{
+    fn parse_from_string(raw: &str) -> Result<Context, Context::Error> {
+        let mut failures = Vec::new();
+
+        let parsed = Parsers::try_parse_sniffed(raw, true, &mut failures)
+            .or_else(|| Parsers::try_parse_sniffed(raw, false, &mut failures));
+
+        match parsed {
+            Some(context) => Ok(context),
+            None => Err(Context::raise_error(ErrAllParsersFailed { failures })),
+        }
+    }
+}
+
+// Cheap check of whether the raw input looks like the format of a parser.
+pub trait SniffFormat {
+    fn looks_like(raw: &str) -> bool;
+}
+
+impl SniffFormat for ParseFromJsonString {
+    fn looks_like(raw: &str) -> bool {
+        matches!(raw.trim_start().chars().next(), Some('{' | '[' | '"'))
+    }
+}
+
+pub trait FallbackParsers<Context>
+where
+    Context: HasErrorType,
+{
+    fn try_parse(raw: &str, failures: &mut Vec<ParserFailure<Context::Error>>) -> Option<Context>;
+}
+
+pub trait SniffingParsers<Context>
+where
+    Context: HasErrorType,
+{
+    // Only tries the parsers for which SniffFormat::looks_like returns `sniffed`.
+    fn try_parse_sniffed(
+        raw: &str,
+        sniffed: bool,
+        failures: &mut Vec<ParserFailure<Context::Error>>,
+    ) -> Option<Context>;
+}
+
+fn try_parser<Context, Parser>(
+    raw: &str,
+    failures: &mut Vec<ParserFailure<Context::Error>>,
+) -> Option<Context>
+where
+    Context: HasErrorType,
+    Parser: StringParser<Context>,
+{
+    match Parser::parse_from_string(raw) {
+        Ok(context) => Some(context),
+        Err(error) => {
+            failures.push(ParserFailure {
+                parser: type_name::<Parser>(),
+                error,
+            });
+
+            None
+        }
+    }
+}
+
+macro_rules! impl_parser_tuple {
+    ( $( $parser:ident ),+ ) => {
+        impl<Context, $( $parser ),+> FallbackParsers<Context> for ( $( $parser, )+ )
+        where
+            Context: HasErrorType,
+            $( $parser: StringParser<Context>, )+
+        {
+            fn try_parse(
+                raw: &str,
+                failures: &mut Vec<ParserFailure<Context::Error>>,
+            ) -> Option<Context> {
+                None
+                $( .or_else(|| try_parser::<Context, $parser>(raw, failures)) )+
+            }
+        }
+
+        impl<Context, $( $parser ),+> SniffingParsers<Context> for ( $( $parser, )+ )
+        where
+            Context: HasErrorType,
+            $( $parser: StringParser<Context> + SniffFormat, )+
+        {
+            fn try_parse_sniffed(
+                raw: &str,
+                sniffed: bool,
+                failures: &mut Vec<ParserFailure<Context::Error>>,
+            ) -> Option<Context> {
+                None
+                $(
+                    .or_else(|| {
+                        if $parser::looks_like(raw) == sniffed {
+                            try_parser::<Context, $parser>(raw, failures)
+                        } else {
+                            None
+                        }
+                    })
+                )+
+            }
+        }
+    };
+}
+
+impl_parser_tuple!(A);
+impl_parser_tuple!(A, B);
+impl_parser_tuple!(A, B, C);
+impl_parser_tuple!(A, B, C, D);
+impl_parser_tuple!(A, B, C, D, E);
+impl_parser_tuple!(A, B, C, D, E, F);
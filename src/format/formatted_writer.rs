@@ -0,0 +1,108 @@
+use core::fmt::{self, Display};
+use core::marker::PhantomData;
+use std::io;
+
+use cgp::prelude::*;
+use serde::Serialize;
+
+use super::string_formatter::StringFormatter;
+
+// Formats a context straight into a writer, instead of allocating a new String for every call.
+//
+// The target is either an IoWriter over a std::io::Write, such as a file or socket,
+// or a FmtWriter over a core::fmt::Write, such as a String.
+#[cgp_component {
+    name: WriterComponent,
+    provider: Writer,
+    context: Context,
+    }]
+pub trait CanWriteFormatted<Target>: HasErrorType {
+    fn write_formatted(&self, target: &mut Target) -> Result<(), Self::Error>;
+}
+
+// The two kinds of writers are wrapped in distinct types, so that a provider can implement
+// Writer for both of them without the implementations overlapping.
+pub struct IoWriter<W>(pub W);
+
+pub struct FmtWriter<W>(pub W);
+
+pub struct WriteAsJson;
+
+impl<Context, W> Writer<Context, IoWriter<W>> for WriteAsJson
+where
+    Context: Serialize + CanRaiseError<serde_json::Error>,
+    W: io::Write,
+{
+    fn write_formatted(context: &Context, target: &mut IoWriter<W>) -> Result<(), Context::Error> {
+        serde_json::to_writer(&mut target.0, context).map_err(Context::raise_error)
+    }
+}
+
+impl<Context, W> Writer<Context, FmtWriter<W>> for WriteAsJson
+where
+    Context: Serialize + CanRaiseError<serde_json::Error>,
+    W: fmt::Write,
+{
+    fn write_formatted(context: &Context, target: &mut FmtWriter<W>) -> Result<(), Context::Error> {
+        serde_json::to_writer(FmtAsIoWriter(&mut target.0), context).map_err(Context::raise_error)
+    }
+}
+
+pub struct WriteWithDisplay;
+
+impl<Context, W> Writer<Context, IoWriter<W>> for WriteWithDisplay
+where
+    Context: Display + CanRaiseError<io::Error>,
+    W: io::Write,
+{
+    fn write_formatted(context: &Context, target: &mut IoWriter<W>) -> Result<(), Context::Error> {
+        write!(target.0, "{context}").map_err(Context::raise_error)
+    }
+}
+
+impl<Context, W> Writer<Context, FmtWriter<W>> for WriteWithDisplay
+where
+    Context: Display + CanRaiseError<fmt::Error>,
+    W: fmt::Write,
+{
+    fn write_formatted(context: &Context, target: &mut FmtWriter<W>) -> Result<(), Context::Error> {
+        write!(target.0, "{context}").map_err(Context::raise_error)
+    }
+}
+
+// Implements StringFormatter on top of any writer provider that can write into a String.
+// For example, FormatWithWriter<WriteAsJson> formats the same output as FormatAsJsonString.
+pub struct FormatWithWriter<Provider>(pub PhantomData<Provider>);
+
+impl<Context, Provider> StringFormatter<Context> for FormatWithWriter<Provider>
+where
+    Context: HasErrorType,
+    Provider: Writer<Context, FmtWriter<String>>,
+{
+    fn format_to_string(context: &Context) -> Result<String, Context::Error> {
+        let mut target = FmtWriter(String::new());
+
+        Provider::write_formatted(context, &mut target)?;
+
+        Ok(target.0)
+    }
+}
+
+// Lets serde_json write into a fmt::Write. serde_json only splits its output
+// at ASCII characters, so every chunk it writes is valid UTF-8 on its own.
+struct FmtAsIoWriter<'a, W>(&'a mut W);
+
+impl<W: fmt::Write> io::Write for FmtAsIoWriter<'_, W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let chunk =
+            core::str::from_utf8(buf).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+
+        self.0.write_str(chunk).map_err(io::Error::other)?;
+
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
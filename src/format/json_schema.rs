@@ -0,0 +1,135 @@
+use core::fmt::{self, Display};
+use core::marker::PhantomData;
+use std::sync::OnceLock;
+
+use cgp::prelude::*;
+use jsonschema::Validator;
+use serde_json::Value;
+
+use super::diagnostic::ParseDiagnostic;
+use super::string_parser::{json_diagnostic, StringParser};
+
+// A JSON Schema that is compiled into a validator the first time it is used.
+// Keep it in a static, so that the schema is only compiled once:
+//
+// impl JsonSchemaGetter<Person> for PersonComponents {
+//     fn json_schema() -> &'static JsonSchema {
+//         static SCHEMA: OnceLock<JsonSchema> = OnceLock::new();
+//         SCHEMA.get_or_init(|| JsonSchema::new(json!({ ... })))
+//     }
+// }
+pub struct JsonSchema {
+    schema: Value,
+    validator: OnceLock<Result<Validator, ErrInvalidJsonSchema>>,
+}
+
+impl JsonSchema {
+    pub fn new(schema: Value) -> Self {
+        Self {
+            schema,
+            validator: OnceLock::new(),
+        }
+    }
+
+    pub fn schema(&self) -> &Value {
+        &self.schema
+    }
+
+    fn validator(&self) -> Result<&Validator, &ErrInvalidJsonSchema> {
+        self.validator
+            .get_or_init(|| {
+                jsonschema::validator_for(&self.schema).map_err(|e| ErrInvalidJsonSchema {
+                    message: e.to_string(),
+                })
+            })
+            .as_ref()
+    }
+}
+
+#[cgp_component {
+    provider: JsonSchemaGetter,
+    }]
+pub trait HasJsonSchema {
+    fn json_schema() -> &'static JsonSchema;
+}
+
+#[derive(Debug, Clone)]
+pub struct ErrInvalidJsonSchema {
+    pub message: String,
+}
+
+impl Display for ErrInvalidJsonSchema {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid JSON schema: {}", self.message)
+    }
+}
+
+impl core::error::Error for ErrInvalidJsonSchema {}
+
+#[derive(Debug)]
+pub struct JsonSchemaViolation {
+    // JSON pointer to the offending value, e.g. /first_name
+    pub pointer: String,
+    pub message: String,
+}
+
+#[derive(Debug)]
+pub struct ErrJsonSchemaViolations {
+    pub violations: Vec<JsonSchemaViolation>,
+}
+
+impl Display for ErrJsonSchemaViolations {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "input does not match the JSON schema:")?;
+
+        for violation in self.violations.iter() {
+            let pointer = if violation.pointer.is_empty() {
+                "/"
+            } else {
+                &violation.pointer
+            };
+
+            write!(f, "\n  {pointer}: {}", violation.message)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl core::error::Error for ErrJsonSchemaViolations {}
+
+// Validates the raw input against the context's JSON schema, and reports every violation
+// before delegating to the inner parser, e.g. ParseWithJsonSchema<ParseFromJsonString>.
+pub struct ParseWithJsonSchema<Inner>(pub PhantomData<Inner>);
+
+impl<Context, Inner> StringParser<Context> for ParseWithJsonSchema<Inner>
+where
+    Context: HasJsonSchema
+        + CanRaiseError<ParseDiagnostic>
+        + CanRaiseError<ErrInvalidJsonSchema>
+        + CanRaiseError<ErrJsonSchemaViolations>,
+    Inner: StringParser<Context>,
+{
+    fn parse_from_string(raw: &str) -> Result<Context, Context::Error> {
+        let validator = Context::json_schema()
+            .validator()
+            .map_err(|e| Context::raise_error(e.clone()))?;
+
+        let value: Value = serde_json::from_str(raw)
+            .map_err(|e| Context::raise_error(json_diagnostic(raw, &e)))?;
+
+        let violations: Vec<JsonSchemaViolation> = validator
+            .iter_errors(&value)
+            .map(|e| JsonSchemaViolation {
+                pointer: e.instance_path.to_string(),
+                message: e.to_string(),
+            })
+            .collect();
+
+        if !violations.is_empty() {
+            return Err(Context::raise_error(ErrJsonSchemaViolations { violations }));
+        }
+
+        Inner::parse_from_string(raw)
+    }
+}
@@ -0,0 +1,152 @@
+use core::fmt::{self, Display};
+use core::marker::PhantomData;
+
+use cgp::prelude::*;
+
+use super::string_parser::StringParser;
+
+// Upper bounds on the raw input that ParseWithLimits lets through to the inner parser.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct ParseLimits {
+    // Maximum length of the whole input, in bytes
+    pub max_input_len: usize,
+    // Maximum number of nested `{`, `[` and `(` brackets
+    pub max_depth: usize,
+    // Maximum length of a single double-quoted string, in bytes and including escapes
+    pub max_string_len: usize,
+}
+
+impl Default for ParseLimits {
+    fn default() -> Self {
+        Self {
+            max_input_len: 1024 * 1024,
+            max_depth: 64,
+            max_string_len: 64 * 1024,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ParseLimit {
+    InputLength,
+    Depth,
+    StringLength,
+}
+
+// Raised when the input goes over one of the parse limits, at the given byte offset.
+#[derive(Debug, Eq, PartialEq)]
+pub struct ErrParseLimitExceeded {
+    pub limit: ParseLimit,
+    pub max: usize,
+    pub offset: usize,
+}
+
+impl Display for ErrParseLimitExceeded {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let limit = match self.limit {
+            ParseLimit::InputLength => "input length",
+            ParseLimit::Depth => "nesting depth",
+            ParseLimit::StringLength => "string length",
+        };
+
+        write!(
+            f,
+            "{} exceeds the limit of {} at offset {}",
+            limit, self.max, self.offset
+        )
+    }
+}
+
+impl core::error::Error for ErrParseLimitExceeded {}
+
+impl ParseLimits {
+    // Scans the input in a single pass without parsing it.
+    // Brackets and strings are recognized the way JSON writes them, which also covers
+    // RON and the flow style of YAML and TOML. Nesting expressed through indentation or
+    // dotted keys is left for the inner parser to bound.
+    pub fn check(&self, input: &str) -> Result<(), ErrParseLimitExceeded> {
+        if input.len() > self.max_input_len {
+            return Err(ErrParseLimitExceeded {
+                limit: ParseLimit::InputLength,
+                max: self.max_input_len,
+                offset: self.max_input_len,
+            });
+        }
+
+        let bytes = input.as_bytes();
+        let mut depth = 0usize;
+        let mut index = 0;
+
+        while index < bytes.len() {
+            match bytes[index] {
+                b'"' => {
+                    let start = index + 1;
+                    index = start;
+
+                    while index < bytes.len() && bytes[index] != b'"' {
+                        index += if bytes[index] == b'\\' { 2 } else { 1 };
+                    }
+
+                    if index.min(bytes.len()) - start > self.max_string_len {
+                        return Err(ErrParseLimitExceeded {
+                            limit: ParseLimit::StringLength,
+                            max: self.max_string_len,
+                            offset: start - 1,
+                        });
+                    }
+                }
+                b'{' | b'[' | b'(' => {
+                    depth += 1;
+
+                    if depth > self.max_depth {
+                        return Err(ErrParseLimitExceeded {
+                            limit: ParseLimit::Depth,
+                            max: self.max_depth,
+                            offset: index,
+                        });
+                    }
+                }
+                b'}' | b']' | b')' => depth = depth.saturating_sub(1),
+                _ => {}
+            }
+
+            index += 1;
+        }
+
+        Ok(())
+    }
+}
+
+#[cgp_component {
+    provider: ParseLimitsGetter,
+    }]
+pub trait HasParseLimits {
+    fn parse_limits() -> ParseLimits;
+}
+
+// Uses ParseLimits::default() as the parse limits of the context.
+pub struct UseDefaultParseLimits;
+
+impl<Context> ParseLimitsGetter<Context> for UseDefaultParseLimits {
+    fn parse_limits() -> ParseLimits {
+        ParseLimits::default()
+    }
+}
+
+// Rejects input that goes over the parse limits of the context before the inner parser
+// gets to see it, e.g. ParseWithLimits<ParseFromJsonString>.
+pub struct ParseWithLimits<Inner>(pub PhantomData<Inner>);
+
+impl<Context, Inner> StringParser<Context> for ParseWithLimits<Inner>
+where
+    Context: HasParseLimits + CanRaiseError<ErrParseLimitExceeded>,
+    Inner: StringParser<Context>,
+{
+    fn parse_from_string(raw: &str) -> Result<Context, Context::Error> {
+        Context::parse_limits()
+            .check(raw)
+            .map_err(Context::raise_error)?;
+
+        Inner::parse_from_string(raw)
+    }
+}
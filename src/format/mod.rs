@@ -0,0 +1,84 @@
+// String formatter and parser components, their binary counterparts that encode to
+// and decode from bytes, a streaming writer component, and the context-generic
+// providers for each of them.
+// JSON is always available, while the other formats are enabled through cargo features.
+//
+// See examples/10-modular-comp for how a context wires them up.
+
+mod bytes_decoder;
+mod bytes_encoder;
+mod canonical_json;
+mod collection;
+mod compressed;
+mod diagnostic;
+mod encoded_string;
+mod fallback_parser;
+mod formatted_writer;
+mod limits;
+mod provided;
+mod redacted;
+mod registry;
+mod string_formatter;
+mod string_parser;
+mod template;
+mod versioned_json;
+
+#[cfg(feature = "bincode")]
+mod bincode_bytes;
+#[cfg(feature = "cbor")]
+mod cbor_bytes;
+#[cfg(feature = "csv")]
+mod csv_collection;
+#[cfg(feature = "digest")]
+mod digest;
+#[cfg(feature = "json-schema")]
+mod json_schema;
+#[cfg(feature = "msgpack")]
+mod msgpack_bytes;
+#[cfg(feature = "ron")]
+mod ron_string;
+#[cfg(feature = "signed")]
+mod signed;
+#[cfg(feature = "toml")]
+mod toml_string;
+#[cfg(feature = "yaml")]
+mod yaml_string;
+
+pub use bytes_decoder::*;
+pub use bytes_encoder::*;
+pub use canonical_json::*;
+pub use collection::*;
+pub use compressed::*;
+pub use diagnostic::*;
+pub use encoded_string::*;
+pub use fallback_parser::*;
+pub use formatted_writer::*;
+pub use limits::*;
+pub use provided::*;
+pub use redacted::*;
+pub use registry::*;
+pub use string_formatter::*;
+pub use string_parser::*;
+pub use template::*;
+pub use versioned_json::*;
+
+#[cfg(feature = "bincode")]
+pub use bincode_bytes::*;
+#[cfg(feature = "cbor")]
+pub use cbor_bytes::*;
+#[cfg(feature = "csv")]
+pub use csv_collection::*;
+#[cfg(feature = "digest")]
+pub use digest::*;
+#[cfg(feature = "json-schema")]
+pub use json_schema::*;
+#[cfg(feature = "msgpack")]
+pub use msgpack_bytes::*;
+#[cfg(feature = "ron")]
+pub use ron_string::*;
+#[cfg(feature = "signed")]
+pub use signed::*;
+#[cfg(feature = "toml")]
+pub use toml_string::*;
+#[cfg(feature = "yaml")]
+pub use yaml_string::*;
@@ -0,0 +1,29 @@
+use cgp::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use super::bytes_decoder::BytesDecoder;
+use super::bytes_encoder::BytesEncoder;
+
+// Encodes structs as maps keyed by field name, so that fields can be added or reordered
+// without breaking previously encoded payloads.
+pub struct EncodeAsMessagePack;
+
+impl<Context> BytesEncoder<Context> for EncodeAsMessagePack
+where
+    Context: Serialize + CanRaiseError<rmp_serde::encode::Error>,
+{
+    fn encode_to_bytes(context: &Context) -> Result<Vec<u8>, Context::Error> {
+        rmp_serde::to_vec_named(context).map_err(Context::raise_error)
+    }
+}
+
+pub struct DecodeFromMessagePack;
+
+impl<Context> BytesDecoder<Context> for DecodeFromMessagePack
+where
+    Context: for<'a> Deserialize<'a> + CanRaiseError<rmp_serde::decode::Error>,
+{
+    fn decode_from_bytes(bytes: &[u8]) -> Result<Context, Context::Error> {
+        rmp_serde::from_slice(bytes).map_err(Context::raise_error)
+    }
+}
@@ -0,0 +1,241 @@
+use core::fmt::{self, Debug, Display};
+use core::marker::PhantomData;
+
+use cgp::prelude::*;
+use serde::ser::SerializeMap;
+use serde::{Serialize, Serializer};
+
+use super::string_formatter::StringFormatter;
+
+// The ProvideSerialize and ProvideFormat provider traits of examples/07-provider-traits,
+// turned into components so that a context can be serialized and displayed through its
+// wiring, without implementing serde::Serialize or Display itself.
+// This is meant for types whose crate does not depend on serde, or that need to be
+// serialized differently than their own implementation does.
+
+#[cgp_component {
+    name: SerializeComponent,
+    provider: ProvideSerialize,
+    context: Context,
+    }]
+pub trait CanSerialize {
+    fn serialize_provided<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error>;
+}
+
+#[cgp_component {
+    name: FormatComponent,
+    provider: ProvideFormat,
+    context: Context,
+    }]
+pub trait CanFormat {
+    fn fmt_provided(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error>;
+}
+
+// The methods are named differently than serde::Serialize::serialize and Display::fmt,
+// so that calling them is not ambiguous on contexts that also implement those traits.
+
+// Wraps a reference to a context, and implements serde::Serialize and Display for it by
+// delegating to the providers wired to the context, e.g. serde_json::to_string(&Provided(&person)).
+pub struct Provided<'a, Context>(pub &'a Context);
+
+impl<Context> Serialize for Provided<'_, Context>
+where
+    Context: CanSerialize,
+{
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.0.serialize_provided(serializer)
+    }
+}
+
+impl<Context> Display for Provided<'_, Context>
+where
+    Context: CanFormat,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt_provided(f)
+    }
+}
+
+// Uses the context's own serde::Serialize implementation.
+pub struct SerializeWithSerde;
+
+impl<Context> ProvideSerialize<Context> for SerializeWithSerde
+where
+    Context: Serialize,
+{
+    fn serialize_provided<S: Serializer>(
+        context: &Context,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        context.serialize(serializer)
+    }
+}
+
+// Uses the context's own Display implementation.
+pub struct UseDisplay;
+
+impl<Context> ProvideFormat<Context> for UseDisplay
+where
+    Context: Display,
+{
+    fn fmt_provided(context: &Context, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+        Display::fmt(context, f)
+    }
+}
+
+// Uses the context's own Debug implementation.
+pub struct UseDebug;
+
+impl<Context> ProvideFormat<Context> for UseDebug
+where
+    Context: Debug,
+{
+    fn fmt_provided(context: &Context, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+        Debug::fmt(context, f)
+    }
+}
+
+// Serializes the listed fields of the context as a map from field name to value,
+// e.g. SerializeFields<Product![symbol!("first_name"), symbol!("last_name")]>.
+// The context only needs to derive HasField, and each field value to implement Serialize.
+pub struct SerializeFields<Fields>(pub PhantomData<Fields>);
+
+impl<Context, Fields> ProvideSerialize<Context> for SerializeFields<Fields>
+where
+    Fields: SerializeFieldList<Context>,
+{
+    fn serialize_provided<S: Serializer>(
+        context: &Context,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(Some(Fields::LEN))?;
+        Fields::serialize_fields(context, &mut map)?;
+        map.end()
+    }
+}
+
+// Displays the listed fields of the context separated by spaces, such as "John Smith"
+// with DisplayFields<Product![symbol!("first_name"), symbol!("last_name")]>.
+pub struct DisplayFields<Fields>(pub PhantomData<Fields>);
+
+impl<Context, Fields> ProvideFormat<Context> for DisplayFields<Fields>
+where
+    Fields: FormatFieldList<Context>,
+{
+    fn fmt_provided(context: &Context, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+        Fields::format_fields(context, f, "")
+    }
+}
+
+// Formats the context as a JSON string through its wired ProvideSerialize provider.
+pub struct FormatProvidedAsJsonString;
+
+impl<Context> StringFormatter<Context> for FormatProvidedAsJsonString
+where
+    Context: CanSerialize + CanRaiseError<serde_json::Error>,
+{
+    fn format_to_string(context: &Context) -> Result<String, Context::Error> {
+        serde_json::to_string(&Provided(context)).map_err(Context::raise_error)
+    }
+}
+
+// Formats the context as a string through its wired ProvideFormat provider.
+pub struct FormatProvidedString;
+
+impl<Context> StringFormatter<Context> for FormatProvidedString
+where
+    Context: CanFormat + HasErrorType,
+{
+    fn format_to_string(context: &Context) -> Result<String, Context::Error> {
+        Ok(Provided(context).to_string())
+    }
+}
+
+// The name of a field, as the type level string that symbol! expands to.
+pub trait FieldName {
+    fn write_name(name: &mut String);
+
+    fn field_name() -> String {
+        let mut name = String::new();
+        Self::write_name(&mut name);
+        name
+    }
+}
+
+impl FieldName for Nil {
+    fn write_name(_name: &mut String) {}
+}
+
+impl<const CHAR: char, Rest> FieldName for Cons<Char<CHAR>, Rest>
+where
+    Rest: FieldName,
+{
+    fn write_name(name: &mut String) {
+        name.push(CHAR);
+        Rest::write_name(name);
+    }
+}
+
+// A type level list of field names, as written with Product!.
+pub trait SerializeFieldList<Context> {
+    const LEN: usize;
+
+    fn serialize_fields<M: SerializeMap>(context: &Context, map: &mut M) -> Result<(), M::Error>;
+}
+
+impl<Context> SerializeFieldList<Context> for Nil {
+    const LEN: usize = 0;
+
+    fn serialize_fields<M: SerializeMap>(_context: &Context, _map: &mut M) -> Result<(), M::Error> {
+        Ok(())
+    }
+}
+
+impl<Context, Tag, Rest> SerializeFieldList<Context> for Cons<Tag, Rest>
+where
+    Context: HasField<Tag>,
+    Context::Value: Serialize,
+    Tag: FieldName,
+    Rest: SerializeFieldList<Context>,
+{
+    const LEN: usize = Rest::LEN + 1;
+
+    fn serialize_fields<M: SerializeMap>(context: &Context, map: &mut M) -> Result<(), M::Error> {
+        map.serialize_entry(&Tag::field_name(), context.get_field(PhantomData::<Tag>))?;
+        Rest::serialize_fields(context, map)
+    }
+}
+
+pub trait FormatFieldList<Context> {
+    fn format_fields(
+        context: &Context,
+        f: &mut fmt::Formatter<'_>,
+        separator: &str,
+    ) -> Result<(), fmt::Error>;
+}
+
+impl<Context> FormatFieldList<Context> for Nil {
+    fn format_fields(
+        _context: &Context,
+        _f: &mut fmt::Formatter<'_>,
+        _separator: &str,
+    ) -> Result<(), fmt::Error> {
+        Ok(())
+    }
+}
+
+impl<Context, Tag, Rest> FormatFieldList<Context> for Cons<Tag, Rest>
+where
+    Context: HasField<Tag>,
+    Context::Value: Display,
+    Rest: FormatFieldList<Context>,
+{
+    fn format_fields(
+        context: &Context,
+        f: &mut fmt::Formatter<'_>,
+        separator: &str,
+    ) -> Result<(), fmt::Error> {
+        write!(f, "{}{}", separator, context.get_field(PhantomData::<Tag>))?;
+        Rest::format_fields(context, f, " ")
+    }
+}
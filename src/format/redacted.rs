@@ -0,0 +1,450 @@
+use core::fmt;
+use core::marker::PhantomData;
+
+use cgp::prelude::*;
+use serde::de::{self, MapAccess, SeqAccess, Visitor};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use serde_json::Value;
+
+use super::string_formatter::{
+    FormatAsJsonString, FormatAsPrettifiedJsonString, FormatStringWithDebug, StringFormatter,
+};
+
+pub const REDACTED: &str = "[REDACTED]";
+
+// The field paths of a context that must not show up in its formatted output.
+// A path is a dot-separated list of field names, where `*` matches any single
+// field, map key or list index, e.g. `last_name` or `auth_tokens_store.*`.
+#[cgp_component {
+    provider: RedactedFieldsGetter,
+    }]
+pub trait HasRedactedFields {
+    fn redacted_fields() -> &'static [&'static str];
+}
+
+// Formats the context with the inner formatter, then replaces the value of every
+// redacted field in the output with `[REDACTED]`.
+// The inner formatter must produce structured output that can be redacted, which is
+// implemented by RedactOutput for FormatAsJsonString, FormatAsPrettifiedJsonString,
+// FormatStringWithDebug, and the YAML, TOML and RON formatters behind their features,
+// e.g. FormatRedacted<FormatAsJsonString>.
+pub struct FormatRedacted<Inner>(pub PhantomData<Inner>);
+
+impl<Context, Inner> StringFormatter<Context> for FormatRedacted<Inner>
+where
+    Context: HasRedactedFields + HasErrorType,
+    Inner: StringFormatter<Context> + RedactOutput<Context>,
+{
+    fn format_to_string(context: &Context) -> Result<String, Context::Error> {
+        let output = Inner::format_to_string(context)?;
+
+        let fields = Context::redacted_fields();
+
+        if fields.is_empty() {
+            Ok(output)
+        } else {
+            Inner::redact_output(output, fields)
+        }
+    }
+}
+
+// Masks the redacted field paths in the output of a formatter.
+pub trait RedactOutput<Context: HasErrorType> {
+    fn redact_output(output: String, fields: &[&str]) -> Result<String, Context::Error>;
+}
+
+impl<Context> RedactOutput<Context> for FormatAsJsonString
+where
+    Context: CanRaiseError<serde_json::Error>,
+{
+    fn redact_output(output: String, fields: &[&str]) -> Result<String, Context::Error> {
+        let value = redact_json(&output, fields).map_err(Context::raise_error)?;
+
+        serde_json::to_string(&value).map_err(Context::raise_error)
+    }
+}
+
+impl<Context> RedactOutput<Context> for FormatAsPrettifiedJsonString
+where
+    Context: CanRaiseError<serde_json::Error>,
+{
+    fn redact_output(output: String, fields: &[&str]) -> Result<String, Context::Error> {
+        let value = redact_json(&output, fields).map_err(Context::raise_error)?;
+
+        serde_json::to_string_pretty(&value).map_err(Context::raise_error)
+    }
+}
+
+impl<Context> RedactOutput<Context> for FormatStringWithDebug
+where
+    Context: HasErrorType,
+{
+    fn redact_output(output: String, fields: &[&str]) -> Result<String, Context::Error> {
+        Ok(DebugRedactor::new(&output, fields).redact())
+    }
+}
+
+fn redact_json(output: &str, fields: &[&str]) -> Result<RedactableValue, serde_json::Error> {
+    let mut value: RedactableValue = serde_json::from_str(output)?;
+
+    value.redact_fields(fields);
+
+    Ok(value)
+}
+
+// A value tree of any self-describing format, which keeps map entries in the order the
+// inner formatter wrote them.
+// serde_json::Value sorts its object keys unless the preserve_order feature of serde_json
+// is enabled, which would change the behavior of serde_json for the whole dependency graph.
+pub(crate) enum RedactableValue {
+    Scalar(Value),
+    List(Vec<RedactableValue>),
+    Map(Vec<(Value, RedactableValue)>),
+}
+
+impl RedactableValue {
+    pub(crate) fn redact_fields(&mut self, fields: &[&str]) {
+        self.redact(&mut Vec::new(), fields);
+    }
+
+    fn redact(&mut self, path: &mut Vec<String>, fields: &[&str]) {
+        if is_redacted(path, fields) {
+            *self = RedactableValue::Scalar(Value::String(REDACTED.to_owned()));
+            return;
+        }
+
+        match self {
+            RedactableValue::Map(entries) => {
+                for (key, entry) in entries.iter_mut() {
+                    path.push(match key {
+                        Value::String(key) => key.clone(),
+                        key => key.to_string(),
+                    });
+                    entry.redact(path, fields);
+                    path.pop();
+                }
+            }
+            RedactableValue::List(items) => {
+                for (index, item) in items.iter_mut().enumerate() {
+                    path.push(index.to_string());
+                    item.redact(path, fields);
+                    path.pop();
+                }
+            }
+            RedactableValue::Scalar(_) => {}
+        }
+    }
+}
+
+impl Serialize for RedactableValue {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            RedactableValue::Scalar(value) => value.serialize(serializer),
+            RedactableValue::List(items) => serializer.collect_seq(items),
+            RedactableValue::Map(entries) => {
+                serializer.collect_map(entries.iter().map(|(key, entry)| (key, entry)))
+            }
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for RedactableValue {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_any(RedactableValueVisitor)
+    }
+}
+
+struct RedactableValueVisitor;
+
+impl<'de> Visitor<'de> for RedactableValueVisitor {
+    type Value = RedactableValue;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("any value")
+    }
+
+    fn visit_bool<E: de::Error>(self, value: bool) -> Result<Self::Value, E> {
+        Ok(RedactableValue::Scalar(Value::Bool(value)))
+    }
+
+    fn visit_i64<E: de::Error>(self, value: i64) -> Result<Self::Value, E> {
+        Ok(RedactableValue::Scalar(Value::from(value)))
+    }
+
+    fn visit_u64<E: de::Error>(self, value: u64) -> Result<Self::Value, E> {
+        Ok(RedactableValue::Scalar(Value::from(value)))
+    }
+
+    fn visit_f64<E: de::Error>(self, value: f64) -> Result<Self::Value, E> {
+        Ok(RedactableValue::Scalar(Value::from(value)))
+    }
+
+    fn visit_str<E: de::Error>(self, value: &str) -> Result<Self::Value, E> {
+        Ok(RedactableValue::Scalar(Value::String(value.to_owned())))
+    }
+
+    fn visit_string<E: de::Error>(self, value: String) -> Result<Self::Value, E> {
+        Ok(RedactableValue::Scalar(Value::String(value)))
+    }
+
+    fn visit_unit<E: de::Error>(self) -> Result<Self::Value, E> {
+        Ok(RedactableValue::Scalar(Value::Null))
+    }
+
+    fn visit_none<E: de::Error>(self) -> Result<Self::Value, E> {
+        Ok(RedactableValue::Scalar(Value::Null))
+    }
+
+    fn visit_some<D: Deserializer<'de>>(self, deserializer: D) -> Result<Self::Value, D::Error> {
+        RedactableValue::deserialize(deserializer)
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+        let mut items = Vec::new();
+
+        while let Some(item) = seq.next_element()? {
+            items.push(item);
+        }
+
+        Ok(RedactableValue::List(items))
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+        let mut entries = Vec::new();
+
+        while let Some((key, entry)) = map.next_entry()? {
+            match key {
+                RedactableValue::Scalar(key) => entries.push((key, entry)),
+                _ => return Err(de::Error::custom("map keys must be scalars")),
+            }
+        }
+
+        Ok(RedactableValue::Map(entries))
+    }
+}
+
+fn is_redacted(path: &[String], fields: &[&str]) -> bool {
+    !path.is_empty()
+        && fields.iter().any(|field| {
+            let segments = field.split('.');
+
+            segments.clone().count() == path.len()
+                && segments
+                    .zip(path)
+                    .all(|(segment, key)| segment == "*" || segment == key)
+        })
+}
+
+// Walks the output of `{:?}` or `{:#?}`, keeping track of the field path of each value,
+// and copies everything except the values of redacted fields.
+// Struct fields and map entries are keyed by their name, with the quotes of string keys
+// removed, while the elements of lists, sets and tuples are keyed by their index.
+struct DebugRedactor<'a> {
+    input: &'a str,
+    fields: &'a [&'a str],
+    pos: usize,
+    output: String,
+    path: Vec<String>,
+}
+
+impl<'a> DebugRedactor<'a> {
+    fn new(input: &'a str, fields: &'a [&'a str]) -> Self {
+        Self {
+            input,
+            fields,
+            pos: 0,
+            output: String::with_capacity(input.len()),
+            path: Vec::new(),
+        }
+    }
+
+    // A custom Debug implementation can print anything, such as an unmatched bracket,
+    // after which the field path of what follows is unknown. So output with unbalanced
+    // brackets is redacted entirely, and if the walk still loses track of the structure,
+    // everything from that point on is redacted instead of copied.
+    fn redact(mut self) -> String {
+        if !self.is_balanced() {
+            return REDACTED.to_owned();
+        }
+
+        let in_sync = self.value().is_some();
+        let rest = &self.input[self.pos..];
+
+        if in_sync && rest.trim().is_empty() {
+            self.output.push_str(rest);
+        } else {
+            self.output.push_str(REDACTED);
+        }
+
+        self.output
+    }
+
+    fn is_balanced(&self) -> bool {
+        let bytes = self.input.as_bytes();
+        let mut open = Vec::new();
+        let mut i = 0;
+
+        while i < bytes.len() {
+            match bytes[i] {
+                quote @ (b'"' | b'\'') => {
+                    i = self.literal_end(i, quote);
+                    continue;
+                }
+                b'{' => open.push(b'}'),
+                b'[' => open.push(b']'),
+                b'(' => open.push(b')'),
+                byte @ (b'}' | b']' | b')') if open.pop() != Some(byte) => return false,
+                _ => {}
+            }
+
+            i += 1;
+        }
+
+        open.is_empty()
+    }
+
+    fn peek(&self) -> Option<u8> {
+        self.input.as_bytes().get(self.pos).copied()
+    }
+
+    fn copy_to(&mut self, end: usize) {
+        self.output.push_str(&self.input[self.pos..end]);
+        self.pos = end;
+    }
+
+    fn copy_whitespace(&mut self) {
+        let rest = &self.input[self.pos..];
+        let end = self.pos + (rest.len() - rest.trim_start().len());
+        self.copy_to(end);
+    }
+
+    // Finds the end of the value starting at `start`, which is the first `,` or closing
+    // bracket outside of any nesting or literal. With `until_colon`, a single `:` also
+    // ends the value, which is used to find the end of a map key.
+    fn scan_end(&self, start: usize, until_colon: bool) -> usize {
+        let bytes = self.input.as_bytes();
+        let mut depth = 0usize;
+        let mut i = start;
+
+        while i < bytes.len() {
+            match bytes[i] {
+                quote @ (b'"' | b'\'') => {
+                    i = self.literal_end(i, quote);
+                    continue;
+                }
+                b'{' | b'[' | b'(' => depth += 1,
+                b'}' | b']' | b')' if depth == 0 => return i,
+                b'}' | b']' | b')' => depth -= 1,
+                b',' if depth == 0 => return i,
+                b':' if bytes.get(i + 1) == Some(&b':') => i += 1,
+                b':' if depth == 0 && until_colon => return i,
+                _ => {}
+            }
+
+            i += 1;
+        }
+
+        i
+    }
+
+    fn literal_end(&self, start: usize, quote: u8) -> usize {
+        let bytes = self.input.as_bytes();
+        let mut i = start + 1;
+
+        while i < bytes.len() {
+            match bytes[i] {
+                b'\\' => i += 2,
+                byte if byte == quote => return i + 1,
+                _ => i += 1,
+            }
+        }
+
+        bytes.len()
+    }
+
+    // Returns None once the output no longer has the expected structure.
+    fn value(&mut self) -> Option<()> {
+        self.copy_whitespace();
+
+        if is_redacted(&self.path, self.fields) {
+            let end = self.scan_end(self.pos, false);
+            let trimmed = self.input[self.pos..end].trim_end().len();
+
+            self.output.push_str(REDACTED);
+            self.pos += trimmed;
+            return Some(());
+        }
+
+        if let Some(quote @ (b'"' | b'\'')) = self.peek() {
+            let end = self.literal_end(self.pos, quote);
+            self.copy_to(end);
+            return Some(());
+        }
+
+        // The type name of a struct, tuple struct or enum variant, or a plain scalar
+        let rest = &self.input[self.pos..];
+        let name_len = rest
+            .find(['{', '[', '(', '}', ']', ')', ','])
+            .unwrap_or(rest.len());
+        self.copy_to(self.pos + name_len);
+
+        match self.peek() {
+            Some(b'{') => self.entries(b'}', true),
+            Some(b'[') => self.entries(b']', false),
+            Some(b'(') => self.entries(b')', false),
+            _ => Some(()),
+        }
+    }
+
+    fn entries(&mut self, close: u8, keyed: bool) -> Option<()> {
+        self.copy_to(self.pos + 1);
+
+        for index in 0.. {
+            self.copy_whitespace();
+
+            match self.peek() {
+                Some(byte) if byte == close => {
+                    self.copy_to(self.pos + 1);
+                    return Some(());
+                }
+                None => return None,
+                _ => {}
+            }
+
+            let key = if keyed {
+                let end = self.scan_end(self.pos, true);
+
+                if self.input.as_bytes().get(end) == Some(&b':') {
+                    let key = self.input[self.pos..end]
+                        .trim()
+                        .trim_matches('"')
+                        .to_owned();
+                    self.copy_to(end + 1);
+                    key
+                } else {
+                    // An element of a set, which has no key
+                    index.to_string()
+                }
+            } else {
+                index.to_string()
+            };
+
+            self.path.push(key);
+            self.value()?;
+            self.path.pop();
+
+            self.copy_whitespace();
+
+            match self.peek() {
+                Some(b',') => self.copy_to(self.pos + 1),
+                Some(byte) if byte == close => {
+                    self.copy_to(self.pos + 1);
+                    return Some(());
+                }
+                _ => return None,
+            }
+        }
+
+        None
+    }
+}
@@ -0,0 +1,191 @@
+use core::fmt::{self, Display};
+
+use cgp::prelude::*;
+
+use super::string_formatter::StringFormatter;
+use super::string_parser::StringParser;
+
+type FormatFn<Context> = fn(&Context) -> Result<String, <Context as HasErrorType>::Error>;
+
+type ParseFn<Context> = fn(&str) -> Result<Context, <Context as HasErrorType>::Error>;
+
+// Picks a formatter or parser at runtime by media type, such as from an Accept or
+// Content-Type header. Each media type is mapped to a statically wired provider:
+//
+// let registry = FormatRegistry::<Person>::new()
+//     .with_formatter::<FormatAsJsonString>("application/json")
+//     .with_formatter::<FormatStringWithDisplay>("text/plain")
+//     .with_parser::<ParseFromJsonString>("application/json");
+//
+// registry.format_as(&person, "text/plain;q=0.5, application/json")?;
+pub struct FormatRegistry<Context>
+where
+    Context: HasErrorType,
+{
+    formatters: Vec<(&'static str, FormatFn<Context>)>,
+    parsers: Vec<(&'static str, ParseFn<Context>)>,
+}
+
+#[derive(Debug)]
+pub struct ErrUnsupportedMediaType {
+    pub media_type: String,
+    pub supported: Vec<&'static str>,
+}
+
+impl Display for ErrUnsupportedMediaType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "unsupported media type `{}`, expected one of: {}",
+            self.media_type,
+            self.supported.join(", ")
+        )
+    }
+}
+
+impl core::error::Error for ErrUnsupportedMediaType {}
+
+impl<Context> Default for FormatRegistry<Context>
+where
+    Context: HasErrorType,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<Context> FormatRegistry<Context>
+where
+    Context: HasErrorType,
+{
+    pub fn new() -> Self {
+        Self {
+            formatters: Vec::new(),
+            parsers: Vec::new(),
+        }
+    }
+
+    pub fn with_formatter<Provider>(mut self, media_type: &'static str) -> Self
+    where
+        Provider: StringFormatter<Context>,
+    {
+        self.formatters
+            .push((media_type, Provider::format_to_string));
+        self
+    }
+
+    pub fn with_parser<Provider>(mut self, media_type: &'static str) -> Self
+    where
+        Provider: StringParser<Context>,
+    {
+        self.parsers.push((media_type, Provider::parse_from_string));
+        self
+    }
+
+    // Returns the registered media type that best matches an Accept header,
+    // honouring q-values and wildcards such as text/* and */*.
+    // As in RFC 9110, each media type takes the q-value of the most specific range that
+    // matches it, so `*/*, application/json;q=0` accepts anything but JSON. Of the media
+    // types with a q-value above 0, the highest wins, and ties go to the one whose range
+    // comes first in the header, then to the one registered first.
+    pub fn negotiate(&self, accept: &str) -> Option<&'static str> {
+        let ranges: Vec<(&str, f32)> = accept
+            .split(',')
+            .filter_map(|range| {
+                let mut parts = range.split(';');
+                let media_range = parts.next()?.trim();
+
+                let quality = parts
+                    .filter_map(|param| param.trim().strip_prefix("q="))
+                    .find_map(|q| q.trim().parse::<f32>().ok())
+                    .unwrap_or(1.0);
+
+                (!media_range.is_empty()).then_some((media_range, quality))
+            })
+            .collect();
+
+        let mut best: Option<(&'static str, f32, usize)> = None;
+
+        for (media_type, _) in &self.formatters {
+            // The first of the most specific ranges that match the media type
+            let matched = ranges
+                .iter()
+                .enumerate()
+                .filter_map(|(index, (media_range, quality))| {
+                    let specificity = media_range_specificity(media_range, media_type)?;
+                    Some((specificity, index, *quality))
+                })
+                .min_by_key(|(specificity, index, _)| (core::cmp::Reverse(*specificity), *index));
+
+            if let Some((_, index, quality)) = matched {
+                let is_better = best.is_none_or(|(_, best_quality, best_index)| {
+                    quality > best_quality || (quality == best_quality && index < best_index)
+                });
+
+                if quality > 0.0 && is_better {
+                    best = Some((media_type, quality, index));
+                }
+            }
+        }
+
+        best.map(|(media_type, _, _)| media_type)
+    }
+
+    pub fn format_as(&self, context: &Context, accept: &str) -> Result<String, Context::Error>
+    where
+        Context: CanRaiseError<ErrUnsupportedMediaType>,
+    {
+        let media_type = self.negotiate(accept).ok_or_else(|| {
+            Context::raise_error(ErrUnsupportedMediaType {
+                media_type: accept.to_owned(),
+                supported: self.formatters.iter().map(|(m, _)| *m).collect(),
+            })
+        })?;
+
+        let (_, format) = self
+            .formatters
+            .iter()
+            .find(|(m, _)| *m == media_type)
+            .expect("negotiated media type is registered");
+
+        format(context)
+    }
+
+    // Parses with the parser registered for a Content-Type header. Parameters such as
+    // charset are ignored, and media types are compared case-insensitively.
+    pub fn parse_as(&self, raw: &str, content_type: &str) -> Result<Context, Context::Error>
+    where
+        Context: CanRaiseError<ErrUnsupportedMediaType>,
+    {
+        let essence = content_type.split(';').next().unwrap_or_default().trim();
+
+        let (_, parse) = self
+            .parsers
+            .iter()
+            .find(|(media_type, _)| media_type.eq_ignore_ascii_case(essence))
+            .ok_or_else(|| {
+                Context::raise_error(ErrUnsupportedMediaType {
+                    media_type: content_type.to_owned(),
+                    supported: self.parsers.iter().map(|(m, _)| *m).collect(),
+                })
+            })?;
+
+        parse(raw)
+    }
+}
+
+// How specifically the media range matches the media type: 2 for the exact type,
+// 1 for a `type/*` range and 0 for `*/*`, or None if it does not match.
+fn media_range_specificity(media_range: &str, media_type: &str) -> Option<u8> {
+    if media_range == "*/*" {
+        return Some(0);
+    }
+
+    match media_range.strip_suffix("/*") {
+        Some(range_type) => media_type
+            .split_once('/')
+            .is_some_and(|(top_level, _)| top_level.eq_ignore_ascii_case(range_type))
+            .then_some(1),
+        None => media_range.eq_ignore_ascii_case(media_type).then_some(2),
+    }
+}
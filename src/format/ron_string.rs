@@ -0,0 +1,66 @@
+use cgp::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use super::diagnostic::ParseDiagnostic;
+use super::fallback_parser::SniffFormat;
+use super::redacted::{RedactOutput, RedactableValue};
+use super::string_formatter::StringFormatter;
+use super::string_parser::StringParser;
+
+pub struct FormatAsRonString;
+
+impl<Context> StringFormatter<Context> for FormatAsRonString
+where
+    Context: Serialize + CanRaiseError<ron::Error>,
+{
+    fn format_to_string(context: &Context) -> Result<String, Context::Error> {
+        ron::to_string(context).map_err(Context::raise_error)
+    }
+}
+
+// The redacted output writes structs as maps keyed by the field names, since the value
+// tree it is redacted on does not know which maps were structs.
+impl<Context> RedactOutput<Context> for FormatAsRonString
+where
+    Context: CanRaiseError<ron::Error>,
+{
+    fn redact_output(output: String, fields: &[&str]) -> Result<String, Context::Error> {
+        let mut value: RedactableValue = ron::from_str(&output)
+            .map_err(|e: ron::error::SpannedError| Context::raise_error(e.code))?;
+
+        value.redact_fields(fields);
+
+        ron::to_string(&value).map_err(Context::raise_error)
+    }
+}
+
+pub struct ParseFromRonString;
+
+impl<Context> StringParser<Context> for ParseFromRonString
+where
+    Context: for<'a> Deserialize<'a> + CanRaiseError<ParseDiagnostic>,
+{
+    fn parse_from_string(ron_str: &str) -> Result<Context, Context::Error> {
+        ron::from_str(ron_str).map_err(|e: ron::error::SpannedError| {
+            Context::raise_error(ParseDiagnostic::at_line_column(
+                "RON",
+                ron_str,
+                e.position.line,
+                e.position.col,
+                e.code.to_string(),
+            ))
+        })
+    }
+}
+
+// RON structs are written either as a bare tuple, or prefixed with the struct name.
+impl SniffFormat for ParseFromRonString {
+    fn looks_like(raw: &str) -> bool {
+        let raw = raw.trim_start();
+        let name_len = raw
+            .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+            .unwrap_or(raw.len());
+
+        raw[name_len..].starts_with('(')
+    }
+}
@@ -0,0 +1,124 @@
+use core::fmt::{self, Display};
+use core::marker::PhantomData;
+
+use cgp::prelude::*;
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+
+use super::string_formatter::StringFormatter;
+use super::string_parser::StringParser;
+
+type HmacSha256 = Hmac<Sha256>;
+
+// Separates the payload from its hex encoded HMAC-SHA256 tag in a signed string.
+pub const SIGNATURE_SEPARATOR: char = '.';
+
+// The secret key that signed payloads of the context are signed and verified with.
+#[cgp_component {
+    provider: SigningKeyGetter,
+    }]
+pub trait HasSigningKey {
+    fn signing_key() -> &'static [u8];
+}
+
+// Raised when a signed string has no tag, or its tag is not 64 hex digits.
+#[derive(Debug)]
+pub struct ErrMalformedSignature;
+
+impl Display for ErrMalformedSignature {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "the signed payload is missing a well-formed HMAC-SHA256 tag"
+        )
+    }
+}
+
+impl core::error::Error for ErrMalformedSignature {}
+
+// Raised when the tag of a signed string does not match its payload,
+// meaning that either the payload was tampered with or it was signed with another key.
+#[derive(Debug)]
+pub struct ErrInvalidSignature;
+
+impl Display for ErrInvalidSignature {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "the HMAC-SHA256 tag does not match the signed payload")
+    }
+}
+
+impl core::error::Error for ErrInvalidSignature {}
+
+// Formats the context with the inner formatter, and appends the HMAC-SHA256 tag of the output,
+// e.g. `{"first_name":"John"}.5d41...` with FormatSigned<FormatAsJsonString>.
+pub struct FormatSigned<Inner>(pub PhantomData<Inner>);
+
+impl<Context, Inner> StringFormatter<Context> for FormatSigned<Inner>
+where
+    Context: HasSigningKey + HasErrorType,
+    Inner: StringFormatter<Context>,
+{
+    fn format_to_string(context: &Context) -> Result<String, Context::Error> {
+        let mut output = Inner::format_to_string(context)?;
+
+        let tag = new_mac(Context::signing_key(), &output)
+            .finalize()
+            .into_bytes();
+
+        output.push(SIGNATURE_SEPARATOR);
+
+        for byte in tag.iter() {
+            output.push_str(&format!("{:02x}", byte));
+        }
+
+        Ok(output)
+    }
+}
+
+// Verifies the HMAC-SHA256 tag of a string produced by FormatSigned in constant time,
+// and only then parses the payload with the inner parser.
+pub struct ParseVerified<Inner>(pub PhantomData<Inner>);
+
+impl<Context, Inner> StringParser<Context> for ParseVerified<Inner>
+where
+    Context:
+        HasSigningKey + CanRaiseError<ErrMalformedSignature> + CanRaiseError<ErrInvalidSignature>,
+    Inner: StringParser<Context>,
+{
+    fn parse_from_string(raw: &str) -> Result<Context, Context::Error> {
+        let (payload, tag) = raw
+            .rsplit_once(SIGNATURE_SEPARATOR)
+            .ok_or_else(|| Context::raise_error(ErrMalformedSignature))?;
+
+        let tag = decode_hex(tag).ok_or_else(|| Context::raise_error(ErrMalformedSignature))?;
+
+        new_mac(Context::signing_key(), payload)
+            .verify_slice(&tag)
+            .map_err(|_| Context::raise_error(ErrInvalidSignature))?;
+
+        Inner::parse_from_string(payload)
+    }
+}
+
+fn new_mac(key: &[u8], payload: &str) -> HmacSha256 {
+    let mut mac = HmacSha256::new_from_slice(key).expect("HMAC accepts keys of any length");
+    mac.update(payload.as_bytes());
+    mac
+}
+
+fn decode_hex(hex: &str) -> Option<[u8; 32]> {
+    let mut bytes = [0; 32];
+
+    if hex.len() != bytes.len() * 2 {
+        return None;
+    }
+
+    // Unlike u8::from_str_radix, only accepts hex digits, and no leading `+`
+    let digit = |digit: u8| char::from(digit).to_digit(16);
+
+    for (byte, digits) in bytes.iter_mut().zip(hex.as_bytes().chunks(2)) {
+        *byte = (digit(digits[0])? << 4 | digit(digits[1])?) as u8;
+    }
+
+    Some(bytes)
+}
@@ -0,0 +1,61 @@
+use core::fmt::{Debug, Display};
+
+use cgp::prelude::*;
+use serde::Serialize;
+
+#[cgp_component {
+    name: StringFormatterComponent,
+    provider: StringFormatter,
+    context: Context,
+    }]
+pub trait CanFormatToString: HasErrorType {
+    fn format_to_string(&self) -> Result<String, Self::Error>;
+}
+
+// Context Generic default implementation for StringFormatter
+pub struct FormatAsJsonString;
+
+impl<Context> StringFormatter<Context> for FormatAsJsonString
+where
+    Context: Serialize + CanRaiseError<serde_json::Error>,
+{
+    fn format_to_string(context: &Context) -> Result<String, Context::Error> {
+        serde_json::to_string(context).map_err(Context::raise_error)
+    }
+}
+
+// Same as FormatAsJsonString, but pretty prints the JSON output
+pub struct FormatAsPrettifiedJsonString;
+
+impl<Context> StringFormatter<Context> for FormatAsPrettifiedJsonString
+where
+    Context: Serialize + CanRaiseError<serde_json::Error>,
+{
+    fn format_to_string(context: &Context) -> Result<String, Context::Error> {
+        serde_json::to_string_pretty(context).map_err(Context::raise_error)
+    }
+}
+
+// Formats the context using its Display implementation
+pub struct FormatStringWithDisplay;
+
+impl<Context> StringFormatter<Context> for FormatStringWithDisplay
+where
+    Context: Display + HasErrorType,
+{
+    fn format_to_string(context: &Context) -> Result<String, Context::Error> {
+        Ok(format!("{}", context))
+    }
+}
+
+// Formats the context using its Debug implementation
+pub struct FormatStringWithDebug;
+
+impl<Context> StringFormatter<Context> for FormatStringWithDebug
+where
+    Context: Debug + HasErrorType,
+{
+    fn format_to_string(context: &Context) -> Result<String, Context::Error> {
+        Ok(format!("{:?}", context))
+    }
+}
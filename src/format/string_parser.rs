@@ -0,0 +1,36 @@
+use cgp::prelude::*;
+use serde::Deserialize;
+
+use super::diagnostic::{strip_location, ParseDiagnostic};
+
+#[cgp_component {
+    name: StringParserComponent,
+    provider: StringParser,
+    context: Context,
+    }]
+pub trait CanParseFromString: Sized + HasErrorType {
+    fn parse_from_string(raw: &str) -> Result<Self, Self::Error>;
+}
+
+// Context Generic default implementation for StringParser
+pub struct ParseFromJsonString;
+
+impl<Context> StringParser<Context> for ParseFromJsonString
+where
+    Context: for<'a> Deserialize<'a> + CanRaiseError<ParseDiagnostic>,
+{
+    fn parse_from_string(json_str: &str) -> Result<Context, Context::Error> {
+        serde_json::from_str(json_str)
+            .map_err(|e| Context::raise_error(json_diagnostic(json_str, &e)))
+    }
+}
+
+pub(crate) fn json_diagnostic(input: &str, error: &serde_json::Error) -> ParseDiagnostic {
+    ParseDiagnostic::at_line_column(
+        "JSON",
+        input,
+        error.line(),
+        error.column(),
+        strip_location(&error.to_string()),
+    )
+}
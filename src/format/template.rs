@@ -0,0 +1,127 @@
+use core::fmt::{self, Display};
+
+use cgp::prelude::*;
+use serde::Serialize;
+use serde_json::Value;
+
+use super::string_formatter::StringFormatter;
+
+// The template that FormatWithTemplate renders the context into, such as
+// "{last_name}, {first_name}". A placeholder names a serialized field of the context,
+// or a dot-separated path into nested fields like "{address.city}".
+// Literal braces are written as "{{" and "}}".
+#[cgp_component {
+    provider: FormatTemplateGetter,
+    }]
+pub trait HasFormatTemplate {
+    fn format_template() -> &'static str;
+}
+
+// Raised when a placeholder of the template names a field that the context does not serialize.
+#[derive(Debug)]
+pub struct ErrMissingTemplateField {
+    pub field: String,
+    pub template: &'static str,
+}
+
+impl Display for ErrMissingTemplateField {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "missing field `{}` used in format template `{}`",
+            self.field, self.template
+        )
+    }
+}
+
+impl core::error::Error for ErrMissingTemplateField {}
+
+// Raised when the template has an unclosed placeholder or an unmatched `}`,
+// with the byte offset where it was found.
+#[derive(Debug)]
+pub struct ErrInvalidTemplate {
+    pub template: &'static str,
+    pub offset: usize,
+}
+
+impl Display for ErrInvalidTemplate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "unmatched brace at offset {} of format template `{}`",
+            self.offset, self.template
+        )
+    }
+}
+
+impl core::error::Error for ErrInvalidTemplate {}
+
+// Renders the serialized fields of the context into the template of HasFormatTemplate.
+// Strings are inserted without quotes, null as an empty string,
+// and nested objects and arrays as JSON.
+pub struct FormatWithTemplate;
+
+impl<Context> StringFormatter<Context> for FormatWithTemplate
+where
+    Context: Serialize
+        + HasFormatTemplate
+        + CanRaiseError<serde_json::Error>
+        + CanRaiseError<ErrMissingTemplateField>
+        + CanRaiseError<ErrInvalidTemplate>,
+{
+    fn format_to_string(context: &Context) -> Result<String, Context::Error> {
+        let template = Context::format_template();
+        let value = serde_json::to_value(context).map_err(Context::raise_error)?;
+
+        let mut output = String::with_capacity(template.len());
+        let mut rest = template;
+
+        while let Some(index) = rest.find(['{', '}']) {
+            output.push_str(&rest[..index]);
+
+            let offset = template.len() - rest.len() + index;
+            let after = &rest[index + 1..];
+
+            if rest[index..].starts_with("{{") {
+                output.push('{');
+                rest = &after[1..];
+            } else if rest[index..].starts_with("}}") {
+                output.push('}');
+                rest = &after[1..];
+            } else if rest[index..].starts_with('}') {
+                return Err(Context::raise_error(ErrInvalidTemplate {
+                    template,
+                    offset,
+                }));
+            } else {
+                let end = after
+                    .find('}')
+                    .ok_or_else(|| Context::raise_error(ErrInvalidTemplate { template, offset }))?;
+
+                let field = after[..end].trim();
+
+                let field_value = field
+                    .split('.')
+                    .try_fold(&value, |value, key| value.get(key))
+                    .ok_or_else(|| {
+                        Context::raise_error(ErrMissingTemplateField {
+                            field: field.to_owned(),
+                            template,
+                        })
+                    })?;
+
+                match field_value {
+                    Value::String(field_value) => output.push_str(field_value),
+                    Value::Null => {}
+                    field_value => output.push_str(&field_value.to_string()),
+                }
+
+                rest = &after[end + 1..];
+            }
+        }
+
+        output.push_str(rest);
+
+        Ok(output)
+    }
+}
@@ -0,0 +1,73 @@
+use cgp::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use super::diagnostic::ParseDiagnostic;
+use super::fallback_parser::SniffFormat;
+use super::redacted::{RedactOutput, RedactableValue};
+use super::string_formatter::StringFormatter;
+use super::string_parser::StringParser;
+
+// TOML documents are tables at the top level, so the context has to serialize as a struct or map.
+pub struct FormatAsTomlString;
+
+impl<Context> StringFormatter<Context> for FormatAsTomlString
+where
+    Context: Serialize + CanRaiseError<toml::ser::Error>,
+{
+    fn format_to_string(context: &Context) -> Result<String, Context::Error> {
+        toml::to_string(context).map_err(Context::raise_error)
+    }
+}
+
+impl<Context> RedactOutput<Context> for FormatAsTomlString
+where
+    Context: CanRaiseError<toml::de::Error> + CanRaiseError<toml::ser::Error>,
+{
+    fn redact_output(output: String, fields: &[&str]) -> Result<String, Context::Error> {
+        let mut value: RedactableValue = toml::from_str(&output).map_err(Context::raise_error)?;
+
+        value.redact_fields(fields);
+
+        toml::to_string(&value).map_err(Context::raise_error)
+    }
+}
+
+pub struct ParseFromTomlString;
+
+impl<Context> StringParser<Context> for ParseFromTomlString
+where
+    Context: for<'a> Deserialize<'a> + CanRaiseError<ParseDiagnostic>,
+{
+    fn parse_from_string(toml_str: &str) -> Result<Context, Context::Error> {
+        toml::from_str(toml_str).map_err(|e: toml::de::Error| {
+            let offset = e.span().map_or(0, |span| span.start);
+
+            Context::raise_error(ParseDiagnostic::at_offset(
+                "TOML",
+                toml_str,
+                offset,
+                e.message(),
+            ))
+        })
+    }
+}
+
+// Sniffs TOML by its first significant line, which is either a table header or a key assignment.
+impl SniffFormat for ParseFromTomlString {
+    fn looks_like(raw: &str) -> bool {
+        raw.lines()
+            .map(str::trim)
+            .find(|line| !line.is_empty() && !line.starts_with('#'))
+            .is_some_and(|line| {
+                line.starts_with('[')
+                    || line.split_once('=').is_some_and(|(key, _)| {
+                        let key = key.trim();
+
+                        !key.is_empty()
+                            && key.chars().all(|c| {
+                                c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '.' | '"')
+                            })
+                    })
+            })
+    }
+}
@@ -0,0 +1,169 @@
+use core::fmt::{self, Display};
+use core::marker::PhantomData;
+
+use cgp::prelude::*;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+use super::diagnostic::ParseDiagnostic;
+use super::string_formatter::StringFormatter;
+use super::string_parser::{json_diagnostic, StringParser};
+
+// The version of the JSON shape that the context currently serializes to.
+#[cgp_component {
+    provider: SchemaVersionGetter,
+    }]
+pub trait HasSchemaVersion {
+    fn schema_version() -> u64;
+}
+
+// Upgrades a JSON value in an older shape, as found in a payload of the given version,
+// and returns the value together with the version it was upgraded to.
+#[cgp_component {
+    provider: JsonMigrator,
+    }]
+pub trait CanMigrateJson: HasErrorType {
+    fn migrate_json(version: u64, value: Value) -> Result<(u64, Value), Self::Error>;
+}
+
+// A single migration step, from FROM_VERSION to FROM_VERSION + 1.
+pub trait JsonMigration<Context>
+where
+    Context: HasErrorType,
+{
+    const FROM_VERSION: u64;
+
+    fn migrate(value: Value) -> Result<Value, Context::Error>;
+}
+
+// Runs a list of migration steps in order, such as
+// MigrationChain<Product![MigrateV1ToV2, MigrateV2ToV3]>.
+// Each step only applies when the payload is at its FROM_VERSION, so a v2 payload
+// skips the first step and a v3 payload skips both.
+pub struct MigrationChain<Migrations>(pub PhantomData<Migrations>);
+
+impl<Context, Migration, Rest> JsonMigrator<Context> for MigrationChain<Cons<Migration, Rest>>
+where
+    Context: HasErrorType,
+    Migration: JsonMigration<Context>,
+    MigrationChain<Rest>: JsonMigrator<Context>,
+{
+    fn migrate_json(version: u64, value: Value) -> Result<(u64, Value), Context::Error> {
+        let (version, value) = if version == Migration::FROM_VERSION {
+            (version + 1, Migration::migrate(value)?)
+        } else {
+            (version, value)
+        };
+
+        MigrationChain::<Rest>::migrate_json(version, value)
+    }
+}
+
+impl<Context> JsonMigrator<Context> for MigrationChain<Nil>
+where
+    Context: HasErrorType,
+{
+    fn migrate_json(version: u64, value: Value) -> Result<(u64, Value), Context::Error> {
+        Ok((version, value))
+    }
+}
+
+#[derive(Debug)]
+pub struct ErrUnsupportedSchemaVersion {
+    pub version: u64,
+    pub current_version: u64,
+}
+
+impl Display for ErrUnsupportedSchemaVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "payload version {} cannot be migrated to the current version {}",
+            self.version, self.current_version
+        )
+    }
+}
+
+impl core::error::Error for ErrUnsupportedSchemaVersion {}
+
+#[derive(Serialize)]
+struct VersionedEnvelope<'a, Data> {
+    version: u64,
+    data: &'a Data,
+}
+
+// Formats the context as JSON wrapped in a {"version":N,"data":...} envelope.
+pub struct FormatAsVersionedJson;
+
+impl<Context> StringFormatter<Context> for FormatAsVersionedJson
+where
+    Context: Serialize + HasSchemaVersion + CanRaiseError<serde_json::Error>,
+{
+    fn format_to_string(context: &Context) -> Result<String, Context::Error> {
+        let envelope = VersionedEnvelope {
+            version: Context::schema_version(),
+            data: context,
+        };
+
+        serde_json::to_string(&envelope).map_err(Context::raise_error)
+    }
+}
+
+// Parses a JSON envelope written by FormatAsVersionedJson, and migrates the data
+// up to the current version before deserializing it.
+//
+// Payloads written before the envelope was introduced are treated as version 1.
+// Malformed JSON is raised as a ParseDiagnostic, while data that does not fit the context
+// after migrating is raised as a serde_json::Error, since it no longer maps to the input.
+pub struct ParseFromVersionedJson;
+
+impl<Context> StringParser<Context> for ParseFromVersionedJson
+where
+    Context: for<'a> Deserialize<'a>
+        + HasSchemaVersion
+        + CanMigrateJson
+        + CanRaiseError<serde_json::Error>
+        + CanRaiseError<ParseDiagnostic>
+        + CanRaiseError<ErrUnsupportedSchemaVersion>,
+{
+    fn parse_from_string(raw: &str) -> Result<Context, Context::Error> {
+        let value: Value = serde_json::from_str(raw)
+            .map_err(|e| Context::raise_error(json_diagnostic(raw, &e)))?;
+
+        let (version, data) = match value {
+            Value::Object(map) if is_envelope(&map) => split_envelope(map),
+            value => (1, value),
+        };
+
+        let current_version = Context::schema_version();
+
+        let (migrated_version, data) = if version < current_version {
+            Context::migrate_json(version, data)?
+        } else {
+            (version, data)
+        };
+
+        if migrated_version != current_version {
+            return Err(Context::raise_error(ErrUnsupportedSchemaVersion {
+                version,
+                current_version,
+            }));
+        }
+
+        serde_json::from_value(data).map_err(Context::raise_error)
+    }
+}
+
+fn is_envelope(map: &Map<String, Value>) -> bool {
+    map.len() == 2 && map.contains_key("data") && map.get("version").is_some_and(Value::is_u64)
+}
+
+fn split_envelope(mut map: Map<String, Value>) -> (u64, Value) {
+    let version = map
+        .get("version")
+        .and_then(Value::as_u64)
+        .unwrap_or_default();
+    let data = map.remove("data").unwrap_or_default();
+
+    (version, data)
+}
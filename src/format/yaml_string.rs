@@ -0,0 +1,67 @@
+use cgp::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use super::diagnostic::{strip_location, ParseDiagnostic};
+use super::fallback_parser::SniffFormat;
+use super::redacted::{RedactOutput, RedactableValue};
+use super::string_formatter::StringFormatter;
+use super::string_parser::StringParser;
+
+pub struct FormatAsYamlString;
+
+impl<Context> StringFormatter<Context> for FormatAsYamlString
+where
+    Context: Serialize + CanRaiseError<serde_yaml::Error>,
+{
+    fn format_to_string(context: &Context) -> Result<String, Context::Error> {
+        serde_yaml::to_string(context).map_err(Context::raise_error)
+    }
+}
+
+impl<Context> RedactOutput<Context> for FormatAsYamlString
+where
+    Context: CanRaiseError<serde_yaml::Error>,
+{
+    fn redact_output(output: String, fields: &[&str]) -> Result<String, Context::Error> {
+        let mut value: RedactableValue =
+            serde_yaml::from_str(&output).map_err(Context::raise_error)?;
+
+        value.redact_fields(fields);
+
+        serde_yaml::to_string(&value).map_err(Context::raise_error)
+    }
+}
+
+pub struct ParseFromYamlString;
+
+impl<Context> StringParser<Context> for ParseFromYamlString
+where
+    Context: for<'a> Deserialize<'a> + CanRaiseError<ParseDiagnostic>,
+{
+    fn parse_from_string(yaml_str: &str) -> Result<Context, Context::Error> {
+        serde_yaml::from_str(yaml_str).map_err(|e| {
+            let offset = e.location().map_or(0, |location| location.index());
+
+            Context::raise_error(ParseDiagnostic::at_offset(
+                "YAML",
+                yaml_str,
+                offset,
+                strip_location(&e.to_string()),
+            ))
+        })
+    }
+}
+
+// YAML is a superset of JSON, so only block style documents are sniffed as YAML.
+impl SniffFormat for ParseFromYamlString {
+    fn looks_like(raw: &str) -> bool {
+        let raw = raw.trim_start();
+
+        raw.starts_with("---")
+            || raw.starts_with("- ")
+            || raw
+                .lines()
+                .next()
+                .is_some_and(|line| line.contains(": ") || line.ends_with(':'))
+    }
+}
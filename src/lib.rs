@@ -1 +1,13 @@
+// Code examples for CGP
+//
+// The examples under examples/ walk through the chapters of
+// https://patterns.contextgeneric.dev one by one. Components that are shared
+// between several chapters live in this library, so that they have a single
+// canonical definition that other crates can depend on.
 
+pub mod auth;
+pub mod error;
+pub mod format;
+
+#[cfg(feature = "testing")]
+pub mod testing;
@@ -0,0 +1,199 @@
+// Test support for checking that the formatter and parser wired to a context agree.
+//
+// Instead of hand picking a value and asserting that it survives a round trip, the
+// round trip is checked over values generated by proptest, which shrinks any failing
+// value down to a minimal one before reporting it.
+
+use core::fmt::Debug;
+use std::panic::{catch_unwind, AssertUnwindSafe};
+
+use proptest::arbitrary::{any, Arbitrary};
+use proptest::collection::vec;
+use proptest::sample::select;
+use proptest::strategy::{BoxedStrategy, Just, Strategy};
+use proptest::test_runner::{Config, TestCaseError, TestRunner};
+use proptest::{prop_assert_eq, prop_oneof};
+
+use crate::format::{CanFormatToString, CanParseFromString, HasParseLimits};
+
+// Checks that parsing the formatted string of a context gives back an equal context,
+// for as many generated contexts as the default proptest config asks for.
+// Panics with the minimal failing context otherwise, e.g. assert_roundtrip::<Person>().
+pub fn assert_roundtrip<Context>()
+where
+    Context: CanFormatToString + CanParseFromString + Arbitrary + PartialEq,
+    Context::Error: Debug,
+{
+    assert_roundtrip_with_config::<Context>(Config::default())
+}
+
+// Same as assert_roundtrip, but with a custom proptest config, such as a different
+// number of cases.
+pub fn assert_roundtrip_with_config<Context>(config: Config)
+where
+    Context: CanFormatToString + CanParseFromString + Arbitrary + PartialEq,
+    Context::Error: Debug,
+{
+    let mut runner = TestRunner::new(config);
+
+    let result = runner.run(&any::<Context>(), |context| {
+        let formatted = context
+            .format_to_string()
+            .map_err(|e| TestCaseError::fail(format!("failed to format: {:?}", e)))?;
+
+        let parsed = Context::parse_from_string(&formatted).map_err(|e| {
+            TestCaseError::fail(format!("failed to parse {:?}: {:?}", formatted, e))
+        })?;
+
+        prop_assert_eq!(parsed, context, "formatted as {:?}", formatted);
+
+        Ok(())
+    });
+
+    if let Err(e) = result {
+        panic!(
+            "round trip of {} failed: {}",
+            core::any::type_name::<Context>(),
+            e
+        );
+    }
+}
+
+// Feeds hostile input to the parser of a context, and checks that it never panics,
+// neither while parsing nor while printing the error it raises.
+// The input is either arbitrary text, one of the seeds as is or with random edits applied,
+// or deeply nested brackets, giant strings and giant numbers.
+// The seeds should be valid input, such as a formatted context, so that the edits
+// reach past the first few bytes of the parser. At least one seed is required.
+// The parser does not have to be guarded by ParseWithLimits, but parsers that recurse
+// without a depth limit of their own can overflow the stack on the nested brackets.
+pub fn assert_parser_is_robust<Context>(seeds: &[&str])
+where
+    Context: CanParseFromString,
+    Context::Error: Debug,
+{
+    assert_parser_is_robust_with_config::<Context>(seeds, Config::default())
+}
+
+// Same as assert_parser_is_robust, but with a custom proptest config.
+pub fn assert_parser_is_robust_with_config<Context>(seeds: &[&str], config: Config)
+where
+    Context: CanParseFromString,
+    Context::Error: Debug,
+{
+    let mut runner = TestRunner::new(config);
+
+    let result = runner.run(&hostile_input(seeds), |input| {
+        catch_unwind(AssertUnwindSafe(|| {
+            if let Err(e) = Context::parse_from_string(&input) {
+                let _ = format!("{:?}", e);
+            }
+        }))
+        .map_err(|_| TestCaseError::fail(format!("parser panicked on {:?}", input)))
+    });
+
+    if let Err(e) = result {
+        panic!(
+            "parser of {} is not robust: {}",
+            core::any::type_name::<Context>(),
+            e
+        );
+    }
+}
+
+// Feeds the same hostile input as assert_parser_is_robust to the parser of a context, and
+// checks that it never accepts input that goes over the parse limits of the context.
+// This is what wiring the parser through ParseWithLimits guarantees, so it catches
+// contexts that declare parse limits but leave their parser unguarded.
+pub fn assert_parser_respects_limits<Context>(seeds: &[&str])
+where
+    Context: CanParseFromString + HasParseLimits,
+{
+    assert_parser_respects_limits_with_config::<Context>(seeds, Config::default())
+}
+
+// Same as assert_parser_respects_limits, but with a custom proptest config.
+pub fn assert_parser_respects_limits_with_config<Context>(seeds: &[&str], config: Config)
+where
+    Context: CanParseFromString + HasParseLimits,
+{
+    let mut runner = TestRunner::new(config);
+    let limits = Context::parse_limits();
+
+    let result = runner.run(&hostile_input(seeds), |input| {
+        if let (Ok(_), Err(e)) = (Context::parse_from_string(&input), limits.check(&input)) {
+            return Err(TestCaseError::fail(format!(
+                "parser accepted input where {}",
+                e
+            )));
+        }
+
+        Ok(())
+    });
+
+    if let Err(e) = result {
+        panic!(
+            "parser of {} does not respect its parse limits: {}",
+            core::any::type_name::<Context>(),
+            e
+        );
+    }
+}
+
+#[derive(Clone, Debug)]
+enum Edit {
+    Delete(usize),
+    Insert(String),
+    Repeat(usize, usize),
+    Truncate,
+}
+
+fn hostile_input(seeds: &[&str]) -> BoxedStrategy<String> {
+    assert!(
+        !seeds.is_empty(),
+        "at least one seed is required, such as a formatted context"
+    );
+
+    let seeds: Vec<String> = seeds.iter().map(|seed| seed.to_string()).collect();
+
+    let edit = prop_oneof![
+        (1..16usize).prop_map(Edit::Delete),
+        any::<String>().prop_map(Edit::Insert),
+        (1..16usize, 2..512usize).prop_map(|(len, times)| Edit::Repeat(len, times)),
+        Just(Edit::Truncate),
+    ];
+
+    let edited_seed =
+        (select(seeds.clone()), vec((any::<usize>(), edit), 1..8)).prop_map(|(seed, edits)| {
+            let mut chars: Vec<char> = seed.chars().collect();
+
+            for (index, edit) in edits {
+                let index = index % (chars.len() + 1);
+
+                match edit {
+                    Edit::Delete(len) => {
+                        chars.drain(index..(index + len).min(chars.len()));
+                    }
+                    Edit::Insert(text) => {
+                        chars.splice(index..index, text.chars());
+                    }
+                    Edit::Repeat(len, times) => {
+                        let slice = chars[index..(index + len).min(chars.len())].to_vec();
+                        let repeated = slice.repeat(times);
+                        chars.splice(index..index, repeated);
+                    }
+                    Edit::Truncate => chars.truncate(index),
+                }
+            }
+
+            chars.into_iter().collect()
+        });
+
+    let oversized = (
+        select(vec!["[", "{\"a\":", "(", "\"", "1", "9e9"]),
+        1..100_000usize,
+    )
+        .prop_map(|(part, times)| part.repeat(times));
+
+    prop_oneof![any::<String>(), select(seeds), edited_seed, oversized].boxed()
+}
@@ -0,0 +1,130 @@
+use std::collections::BTreeMap;
+
+use cgp::core::error::{ErrorRaiserComponent, ErrorTypeComponent};
+use cgp::core::field::impls::use_field::UseField;
+use cgp::prelude::*;
+use cgp_examples::auth::*;
+use cgp_examples::error::{ErrorWrapperComponent, IgnoreErrorDetail};
+
+// Mock context whose current time is read from a MockClock, so that the tests are deterministic.
+#[derive(HasField)]
+pub struct MockApp {
+    pub clock: MockClock<u64>,
+    pub auth_tokens_store: BTreeMap<String, u64>,
+}
+
+pub struct MockAppComponents;
+
+impl HasComponents for MockApp {
+    type Components = MockAppComponents;
+}
+
+delegate_components! {
+    MockAppComponents {
+        ErrorTypeComponent: UseAuthError,
+        ErrorRaiserComponent: RaiseAuthError,
+        ErrorWrapperComponent: IgnoreErrorDetail,
+        TimeTypeComponent: GetSystemTimestamp,
+        CurrentTimeGetterComponent: UseMockClock,
+        MockClockGetterComponent: UseField<symbol!("clock")>,
+        AuthTokenTypeComponent: UseStringAuthToken,
+        AuthTokenValidatorComponent: ValidateTokenIsNotExpired,
+    }
+}
+
+impl AuthTokenExpiryFetcher<MockApp> for MockAppComponents {
+    fn fetch_auth_token_expiry(
+        context: &MockApp,
+        auth_token: &String,
+    ) -> Result<u64, AuthError<u64>> {
+        context
+            .auth_tokens_store
+            .get(auth_token)
+            .cloned()
+            .ok_or_else(|| MockApp::raise_error(ErrUnknownAuthToken))
+    }
+}
+
+fn mock_app(now: u64) -> MockApp {
+    MockApp {
+        clock: MockClock::new(now),
+        auth_tokens_store: BTreeMap::from([("token".to_owned(), 1_000)]),
+    }
+}
+
+#[test]
+fn test_token_before_expiry_is_valid() {
+    assert!(mock_app(999).validate_auth_token(&"token".into()).is_ok());
+}
+
+#[test]
+fn test_token_at_expiry_is_expired() {
+    let err = mock_app(1_000)
+        .validate_auth_token(&"token".into())
+        .unwrap_err();
+
+    assert!(matches!(
+        err,
+        AuthError::AuthTokenHasExpired(ErrAuthTokenHasExpired {
+            expiry: 1_000,
+            now: 1_000
+        })
+    ));
+}
+
+#[test]
+fn test_token_after_expiry_is_expired() {
+    let err = mock_app(1_001)
+        .validate_auth_token(&"token".into())
+        .unwrap_err();
+
+    assert!(matches!(
+        err,
+        AuthError::AuthTokenHasExpired(ErrAuthTokenHasExpired {
+            expiry: 1_000,
+            now: 1_001
+        })
+    ));
+    assert_eq!(
+        err.to_string(),
+        "auth token has expired at 1000, current time is 1001"
+    );
+}
+
+#[test]
+fn test_unknown_token_is_rejected() {
+    let err = mock_app(0)
+        .validate_auth_token(&"unknown".into())
+        .unwrap_err();
+
+    assert!(matches!(
+        err,
+        AuthError::UnknownAuthToken(ErrUnknownAuthToken)
+    ));
+}
+
+#[test]
+fn test_token_expires_when_clock_advances() {
+    let app = mock_app(999);
+    assert!(app.validate_auth_token(&"token".into()).is_ok());
+
+    app.clock.advance(1);
+    assert!(app.validate_auth_token(&"token".into()).is_err());
+
+    app.clock.set(0);
+    assert!(app.validate_auth_token(&"token".into()).is_ok());
+}
+
+#[test]
+fn test_frozen_clock_ignores_advance() {
+    let app = mock_app(999);
+
+    app.clock.freeze();
+    app.clock.advance(1);
+    assert_eq!(app.current_time().unwrap(), 999);
+    assert!(app.validate_auth_token(&"token".into()).is_ok());
+
+    app.clock.unfreeze();
+    app.clock.advance(1);
+    assert_eq!(app.current_time().unwrap(), 1_000);
+}
@@ -0,0 +1,113 @@
+// Round trips Person through each of the bytes formats that are enabled
+#![cfg(any(
+    all(feature = "cbor", feature = "base64"),
+    all(feature = "msgpack", feature = "hex"),
+    all(feature = "bincode", feature = "base64"),
+))]
+
+#[macro_use]
+mod common;
+
+use cgp::core::error::{ErrorRaiserComponent, ErrorTypeComponent};
+use cgp::prelude::*;
+use cgp_examples::error::{RaiseIntoAnyhow, UseAnyhowError};
+use cgp_examples::format::*;
+use serde::{Deserialize, Serialize};
+
+// Contexts that share the Person fields, but are wired to a different bytes format,
+// and to a string formatter and parser derived from it.
+define_contexts! {
+    #[derive(Serialize, Deserialize, Debug, Eq, PartialEq)]
+    pub struct {
+        pub first_name: String,
+        pub last_name: String,
+    }
+
+    impl<Context, Components> {
+        impl Default for Context {
+            fn default() -> Self {
+                Self {
+                    first_name: "John".into(),
+                    last_name: "Smith".into(),
+                }
+            }
+        }
+    }
+
+    #[cfg(all(feature = "cbor", feature = "base64"))]
+    CborPerson: CborPersonComponents {
+        ErrorTypeComponent: UseAnyhowError,
+        ErrorRaiserComponent: RaiseIntoAnyhow,
+        BytesEncoderComponent: EncodeAsCbor,
+        BytesDecoderComponent: DecodeFromCbor,
+        StringFormatterComponent: FormatAsEncodedString<EncodeAsCbor, Base64>,
+        StringParserComponent: ParseFromEncodedString<DecodeFromCbor, Base64>,
+    }
+
+    #[cfg(all(feature = "msgpack", feature = "hex"))]
+    MessagePackPerson: MessagePackPersonComponents {
+        ErrorTypeComponent: UseAnyhowError,
+        ErrorRaiserComponent: RaiseIntoAnyhow,
+        BytesEncoderComponent: EncodeAsMessagePack,
+        BytesDecoderComponent: DecodeFromMessagePack,
+        StringFormatterComponent: FormatAsEncodedString<EncodeAsMessagePack, Hex>,
+        StringParserComponent: ParseFromEncodedString<DecodeFromMessagePack, Hex>,
+    }
+
+    #[cfg(all(feature = "bincode", feature = "base64"))]
+    BincodePerson: BincodePersonComponents {
+        ErrorTypeComponent: UseAnyhowError,
+        ErrorRaiserComponent: RaiseIntoAnyhow,
+        BytesEncoderComponent: EncodeAsBincode,
+        BytesDecoderComponent: DecodeFromBincode,
+        StringFormatterComponent: FormatAsEncodedString<EncodeAsBincode, Base64>,
+        StringParserComponent: ParseFromEncodedString<DecodeFromBincode, Base64>,
+    }
+}
+
+#[cfg(all(feature = "cbor", feature = "base64"))]
+#[test]
+fn test_cbor_round_trip() {
+    let person = CborPerson::default();
+
+    let bytes = person.encode_to_bytes().unwrap();
+    assert_eq!(CborPerson::decode_from_bytes(&bytes).unwrap(), person);
+
+    let person_str = person.format_to_string().unwrap();
+    assert_eq!(CborPerson::parse_from_string(&person_str).unwrap(), person);
+    assert!(CborPerson::parse_from_string("not base64!").is_err());
+}
+
+#[cfg(all(feature = "msgpack", feature = "hex"))]
+#[test]
+fn test_msgpack_round_trip() {
+    let person = MessagePackPerson::default();
+
+    let bytes = person.encode_to_bytes().unwrap();
+    assert_eq!(
+        MessagePackPerson::decode_from_bytes(&bytes).unwrap(),
+        person
+    );
+
+    let person_str = person.format_to_string().unwrap();
+    assert!(person_str.chars().all(|c| c.is_ascii_hexdigit()));
+    assert_eq!(
+        MessagePackPerson::parse_from_string(&person_str).unwrap(),
+        person
+    );
+}
+
+#[cfg(all(feature = "bincode", feature = "base64"))]
+#[test]
+fn test_bincode_round_trip() {
+    let person = BincodePerson::default();
+
+    let bytes = person.encode_to_bytes().unwrap();
+    assert_eq!(BincodePerson::decode_from_bytes(&bytes).unwrap(), person);
+
+    let person_str = person.format_to_string().unwrap();
+    assert_eq!(
+        BincodePerson::parse_from_string(&person_str).unwrap(),
+        person
+    );
+}
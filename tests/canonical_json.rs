@@ -0,0 +1,183 @@
+use cgp::core::error::{ErrorRaiserComponent, ErrorTypeComponent};
+use cgp::prelude::*;
+use cgp_examples::error::{RaiseIntoAnyhow, UseAnyhowError};
+use cgp_examples::format::*;
+use serde::Serialize;
+use serde_json::{json, Value};
+
+// Declares its fields in the opposite order of PersonRecord below.
+#[derive(Serialize)]
+pub struct Person {
+    pub last_name: String,
+    pub first_name: String,
+}
+
+#[derive(Serialize)]
+pub struct PersonRecord {
+    pub first_name: String,
+    pub last_name: String,
+}
+
+pub struct PersonComponents;
+
+impl HasComponents for Person {
+    type Components = PersonComponents;
+}
+
+impl HasComponents for PersonRecord {
+    type Components = PersonComponents;
+}
+
+delegate_components! {
+    PersonComponents {
+        ErrorTypeComponent: UseAnyhowError,
+        ErrorRaiserComponent: RaiseIntoAnyhow,
+        StringFormatterComponent: FormatAsCanonicalJson,
+    }
+}
+
+#[cfg(feature = "digest")]
+delegate_components! {
+    PersonComponents {
+        DigestComputerComponent: DigestWithSha256<FormatAsCanonicalJson>,
+    }
+}
+
+fn person() -> Person {
+    Person {
+        last_name: "Smith".into(),
+        first_name: "John".into(),
+    }
+}
+
+fn person_record() -> PersonRecord {
+    PersonRecord {
+        first_name: "John".into(),
+        last_name: "Smith".into(),
+    }
+}
+
+#[test]
+fn test_format_sorts_keys() {
+    assert_eq!(
+        person().format_to_string().unwrap(),
+        r#"{"first_name":"John","last_name":"Smith"}"#
+    );
+    assert_eq!(
+        person().format_to_string().unwrap(),
+        person_record().format_to_string().unwrap()
+    );
+}
+
+// The example of section 3.2.2 of RFC 8785
+#[test]
+fn test_canonicalize_rfc_example() {
+    let value: Value = serde_json::from_str(
+        r#"{
+            "numbers": [333333333.33333329, 1E30, 4.50, 2e-3, 0.000000000000000000000000001],
+            "string": "\u20ac$\u000F\u000aA'\u0042\u0022\u005c\\\"\/",
+            "literals": [null, true, false]
+        }"#,
+    )
+    .unwrap();
+
+    assert_eq!(
+        to_canonical_json(&value).unwrap(),
+        r#"{"literals":[null,true,false],"numbers":[333333333.3333333,1e+30,4.5,0.002,1e-27],"string":"€$\u000f\nA'B\"\\\\\"/"}"#
+    );
+}
+
+// The sorting example of section 3.2.3 of RFC 8785
+#[test]
+fn test_sort_keys_by_utf16_code_units() {
+    let value: Value = serde_json::from_str(
+        r#"{
+            "€": "Euro Sign",
+            "\r": "Carriage Return",
+            "דּ": "Hebrew Letter Dalet With Dagesh",
+            "1": "One",
+            "😀": "Emoji: Grinning Face",
+            "\u0080": "Control",
+            "ö": "Latin Small Letter O With Diaeresis"
+        }"#,
+    )
+    .unwrap();
+
+    let canonical = to_canonical_json(&value).unwrap();
+    let positions: Vec<usize> = [
+        "Carriage Return",
+        "One",
+        "Control",
+        "Latin Small",
+        "Euro Sign",
+        "Emoji",
+        "Hebrew",
+    ]
+    .iter()
+    .map(|name| canonical.find(name).unwrap())
+    .collect();
+
+    assert!(positions.windows(2).all(|pair| pair[0] < pair[1]));
+}
+
+#[test]
+fn test_format_numbers() {
+    let numbers = json!([
+        0,
+        -0.0,
+        1,
+        -1.5,
+        1e20,
+        1e21,
+        0.000001,
+        1e-7,
+        123456789012345680000.0,
+        9007199254740992u64,
+        -9007199254740992i64,
+        18446744073709549568u64,
+        f64::MAX,
+        f64::MIN_POSITIVE,
+    ]);
+
+    assert_eq!(
+        to_canonical_json(&numbers).unwrap(),
+        "[0,0,1,-1.5,100000000000000000000,1e+21,0.000001,1e-7,123456789012345680000,\
+         9007199254740992,-9007199254740992,18446744073709550000,1.7976931348623157e+308,2.2250738585072014e-308]"
+    );
+}
+
+#[test]
+fn test_inexact_integers_are_rejected() {
+    for number in [
+        json!(9007199254740993u64),
+        json!(u64::MAX),
+        json!(-9007199254740993i64),
+        json!([1, { "id": i64::MAX }]),
+    ] {
+        let err = to_canonical_json(&number).unwrap_err();
+        assert!(matches!(err, ErrNumberOutOfRange { .. }));
+    }
+
+    assert_eq!(
+        to_canonical_json(&json!(9007199254740993u64)).unwrap_err(),
+        ErrNumberOutOfRange {
+            number: "9007199254740993".into()
+        }
+    );
+}
+
+#[cfg(feature = "digest")]
+#[test]
+fn test_compute_digest_of_canonical_form() {
+    let digest = person().compute_digest().unwrap();
+
+    assert_eq!(
+        digest.to_string(),
+        "e01a75c7bd828b3d1c622049dd54b07fc79b2ae9abc173a7a21972b217bee315"
+    );
+    assert_eq!(digest, person_record().compute_digest().unwrap());
+    assert_eq!(
+        digest.to_etag(),
+        "\"e01a75c7bd828b3d1c622049dd54b07fc79b2ae9abc173a7a21972b217bee315\""
+    );
+}
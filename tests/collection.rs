@@ -0,0 +1,147 @@
+#![cfg(feature = "csv")]
+
+use cgp::core::error::{ErrorRaiserComponent, ErrorTypeComponent};
+use cgp::prelude::*;
+use cgp_examples::error::{RaiseIntoAnyhow, UseAnyhowError};
+use cgp_examples::format::*;
+use serde::{Deserialize, Serialize};
+
+// The same Person context, once wired to CSV and once to TSV.
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+pub struct CsvPerson {
+    pub first_name: String,
+    pub last_name: String,
+    pub age: u8,
+}
+
+pub struct CsvPersonComponents;
+
+impl HasComponents for CsvPerson {
+    type Components = CsvPersonComponents;
+}
+
+delegate_components! {
+    CsvPersonComponents {
+        ErrorTypeComponent: UseAnyhowError,
+        ErrorRaiserComponent: RaiseIntoAnyhow,
+        CollectionFormatterComponent: FormatAsCsv,
+        CollectionParserComponent: ParseFromCsv,
+    }
+}
+
+impl CsvPerson {
+    fn people() -> Vec<Self> {
+        vec![
+            Self {
+                first_name: "John".into(),
+                last_name: "Smith".into(),
+                age: 42,
+            },
+            Self {
+                first_name: "Jane \"JJ\"".into(),
+                last_name: "Doe,\tJr.".into(),
+                age: 37,
+            },
+        ]
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+pub struct TsvPerson {
+    pub first_name: String,
+    pub last_name: String,
+    pub age: u8,
+}
+
+pub struct TsvPersonComponents;
+
+impl HasComponents for TsvPerson {
+    type Components = TsvPersonComponents;
+}
+
+delegate_components! {
+    TsvPersonComponents {
+        ErrorTypeComponent: UseAnyhowError,
+        ErrorRaiserComponent: RaiseIntoAnyhow,
+        CollectionFormatterComponent: FormatAsTsv,
+        CollectionParserComponent: ParseFromTsv,
+    }
+}
+
+impl TsvPerson {
+    fn people() -> Vec<Self> {
+        vec![
+            Self {
+                first_name: "John".into(),
+                last_name: "Smith".into(),
+                age: 42,
+            },
+            Self {
+                first_name: "Jane \"JJ\"".into(),
+                last_name: "Doe,\tJr.".into(),
+                age: 37,
+            },
+        ]
+    }
+}
+
+#[test]
+fn test_csv_roundtrip() {
+    let people = CsvPerson::people();
+
+    let csv = CsvPerson::format_collection(&people).unwrap();
+    assert_eq!(
+        csv,
+        "first_name,last_name,age\nJohn,Smith,42\n\"Jane \"\"JJ\"\"\",\"Doe,\tJr.\",37\n"
+    );
+
+    assert_eq!(CsvPerson::parse_collection(&csv).unwrap(), people);
+}
+
+#[test]
+fn test_tsv_roundtrip() {
+    let people = TsvPerson::people();
+
+    let tsv = TsvPerson::format_collection(&people).unwrap();
+    assert_eq!(
+        tsv,
+        "first_name\tlast_name\tage\nJohn\tSmith\t42\n\"Jane \"\"JJ\"\"\"\t\"Doe,\tJr.\"\t37\n"
+    );
+
+    assert_eq!(TsvPerson::parse_collection(&tsv).unwrap(), people);
+}
+
+#[test]
+fn test_columns_are_matched_by_header() {
+    let csv = "age,last_name,first_name\n42,Smith,John\n";
+
+    let people = CsvPerson::parse_collection(csv).unwrap();
+    assert_eq!(people[0].first_name, "John");
+    assert_eq!(people[0].age, 42);
+}
+
+#[test]
+fn test_invalid_value_reports_row_and_column() {
+    let csv = "first_name,last_name,age\nJohn,Smith,42\nJane,Doe,old\n";
+
+    let err = CsvPerson::parse_collection(csv).unwrap_err();
+    let err = err.downcast_ref::<ErrInvalidRow>().unwrap();
+
+    assert_eq!(err.row, 3);
+    assert_eq!(err.column, Some(3));
+    assert_eq!(
+        err.to_string(),
+        "invalid value at row 3, column 3: invalid digit found in string"
+    );
+}
+
+#[test]
+fn test_missing_field_reports_row() {
+    let csv = "first_name,last_name,age\nJohn,Smith\n";
+
+    let err = CsvPerson::parse_collection(csv).unwrap_err();
+    let err = err.downcast_ref::<ErrInvalidRow>().unwrap();
+
+    assert_eq!(err.row, 2);
+    assert_eq!(err.column, None);
+}
@@ -0,0 +1,91 @@
+// Defines contexts that share the same fields, but are each wired to their own providers.
+// Every context gets the struct with the given fields, its components struct, and the
+// HasComponents impl, while the entries of its delegate_components! are written out as is.
+// The items in the optional `impl<Context, Components> { ... }` block are added for every
+// context, with the two names given there standing for the context and its components.
+//
+// define_contexts! {
+//     #[derive(Serialize, Deserialize)]
+//     pub struct {
+//         pub first_name: String,
+//     }
+//
+//     impl<Context, Components> {
+//         impl Default for Context { ... }
+//     }
+//
+//     JsonPerson: JsonPersonComponents {
+//         ErrorTypeComponent: UseAnyhowError,
+//         ErrorRaiserComponent: RaiseIntoAnyhow,
+//         StringParserComponent: ParseFromJsonString,
+//     }
+//
+//     #[cfg(feature = "yaml")]
+//     YamlPerson: YamlPersonComponents {
+//         ErrorTypeComponent: UseAnyhowError,
+//         ErrorRaiserComponent: RaiseIntoAnyhow,
+//         StringParserComponent: ParseFromYamlString,
+//     }
+// }
+#[allow(unused_macros)]
+macro_rules! define_contexts {
+    (
+        #[derive $derive:tt]
+        pub struct $fields:tt
+
+        impl<$context_alias:ident, $components_alias:ident> $items:tt
+
+        $(
+            $(#[cfg $cfg:tt])?
+            $context:ident: $components:ident $wiring:tt
+        )*
+    ) => {
+        $(
+            define_contexts!(@context [$(#[cfg $cfg])?] #[derive $derive] $context $fields $components $wiring);
+
+            $(#[cfg $cfg])?
+            const _: () = {
+                #[allow(dead_code)]
+                type $context_alias = $context;
+                #[allow(dead_code)]
+                type $components_alias = $components;
+
+                define_contexts!(@items $items);
+            };
+        )*
+    };
+    (
+        #[derive $derive:tt]
+        pub struct $fields:tt
+
+        $(
+            $(#[cfg $cfg:tt])?
+            $context:ident: $components:ident $wiring:tt
+        )*
+    ) => {
+        $(
+            define_contexts!(@context [$(#[cfg $cfg])?] #[derive $derive] $context $fields $components $wiring);
+        )*
+    };
+    (@context [$($cfg:tt)*] #[derive $derive:tt] $context:ident $fields:tt $components:ident $wiring:tt) => {
+        $($cfg)*
+        #[derive $derive]
+        pub struct $context $fields
+
+        $($cfg)*
+        pub struct $components;
+
+        $($cfg)*
+        impl ::cgp::prelude::HasComponents for $context {
+            type Components = $components;
+        }
+
+        $($cfg)*
+        ::cgp::prelude::delegate_components! {
+            $components $wiring
+        }
+    };
+    (@items { $($item:item)* }) => {
+        $($item)*
+    };
+}
@@ -0,0 +1,192 @@
+// Round trips Person through each of the compression algorithms that are enabled
+#![cfg(any(feature = "gzip", feature = "zstd"))]
+
+use cgp::core::error::{ErrorRaiserComponent, ErrorTypeComponent};
+use cgp::prelude::*;
+use cgp_examples::error::{RaiseIntoAnyhow, UseAnyhowError};
+use cgp_examples::format::*;
+use serde::{Deserialize, Serialize};
+
+// The same Person context, compressing its JSON with gzip or with zstd
+// once it is at least 64 bytes long, and decompressing it up to 4 KiB.
+#[cfg(feature = "gzip")]
+#[derive(Serialize, Deserialize, Debug, Eq, PartialEq)]
+pub struct GzipPerson {
+    pub first_name: String,
+    pub last_name: String,
+    pub bio: String,
+}
+
+#[cfg(feature = "gzip")]
+pub struct GzipPersonComponents;
+
+#[cfg(feature = "gzip")]
+impl HasComponents for GzipPerson {
+    type Components = GzipPersonComponents;
+}
+
+#[cfg(feature = "gzip")]
+delegate_components! {
+    GzipPersonComponents {
+        ErrorTypeComponent: UseAnyhowError,
+        ErrorRaiserComponent: RaiseIntoAnyhow,
+        BytesEncoderComponent: Compressed<EncodeAsUtf8<FormatAsJsonString>, Gzip>,
+        BytesDecoderComponent: Compressed<DecodeFromUtf8<ParseFromJsonString>, Gzip>,
+    }
+}
+
+#[cfg(feature = "gzip")]
+impl CompressionThresholdGetter<GzipPerson> for GzipPersonComponents {
+    fn compression_threshold() -> usize {
+        64
+    }
+}
+
+#[cfg(feature = "gzip")]
+impl MaxDecompressedSizeGetter<GzipPerson> for GzipPersonComponents {
+    fn max_decompressed_size() -> usize {
+        4096
+    }
+}
+
+#[cfg(feature = "gzip")]
+impl GzipPerson {
+    fn with_bio(bio: &str) -> Self {
+        Self {
+            first_name: "John".into(),
+            last_name: "Smith".into(),
+            bio: bio.into(),
+        }
+    }
+}
+
+#[cfg(feature = "zstd")]
+#[derive(Serialize, Deserialize, Debug, Eq, PartialEq)]
+pub struct ZstdPerson {
+    pub first_name: String,
+    pub last_name: String,
+    pub bio: String,
+}
+
+#[cfg(feature = "zstd")]
+pub struct ZstdPersonComponents;
+
+#[cfg(feature = "zstd")]
+impl HasComponents for ZstdPerson {
+    type Components = ZstdPersonComponents;
+}
+
+#[cfg(feature = "zstd")]
+delegate_components! {
+    ZstdPersonComponents {
+        ErrorTypeComponent: UseAnyhowError,
+        ErrorRaiserComponent: RaiseIntoAnyhow,
+        BytesEncoderComponent: Compressed<EncodeAsUtf8<FormatAsJsonString>, Zstd>,
+        BytesDecoderComponent: Compressed<DecodeFromUtf8<ParseFromJsonString>, Zstd>,
+    }
+}
+
+#[cfg(feature = "zstd")]
+impl CompressionThresholdGetter<ZstdPerson> for ZstdPersonComponents {
+    fn compression_threshold() -> usize {
+        64
+    }
+}
+
+#[cfg(feature = "zstd")]
+impl MaxDecompressedSizeGetter<ZstdPerson> for ZstdPersonComponents {
+    fn max_decompressed_size() -> usize {
+        4096
+    }
+}
+
+#[cfg(feature = "zstd")]
+impl ZstdPerson {
+    fn with_bio(bio: &str) -> Self {
+        Self {
+            first_name: "John".into(),
+            last_name: "Smith".into(),
+            bio: bio.into(),
+        }
+    }
+}
+
+#[cfg(feature = "gzip")]
+#[test]
+fn test_gzip_roundtrip() {
+    let person = GzipPerson::with_bio(&"likes long walks ".repeat(100));
+
+    let bytes = person.encode_to_bytes().unwrap();
+    assert!(bytes.starts_with(Gzip::MAGIC));
+    assert!(bytes.len() < person.bio.len());
+
+    assert_eq!(GzipPerson::decode_from_bytes(&bytes).unwrap(), person);
+}
+
+#[cfg(feature = "zstd")]
+#[test]
+fn test_zstd_roundtrip() {
+    let person = ZstdPerson::with_bio(&"likes long walks ".repeat(100));
+
+    let bytes = person.encode_to_bytes().unwrap();
+    assert!(bytes.starts_with(Zstd::MAGIC));
+    assert!(bytes.len() < person.bio.len());
+
+    assert_eq!(ZstdPerson::decode_from_bytes(&bytes).unwrap(), person);
+}
+
+#[cfg(feature = "gzip")]
+#[test]
+fn test_small_payload_is_not_compressed() {
+    let person = GzipPerson::with_bio("");
+
+    let bytes = person.encode_to_bytes().unwrap();
+    assert_eq!(
+        bytes,
+        br#"{"first_name":"John","last_name":"Smith","bio":""}"#
+    );
+
+    assert_eq!(GzipPerson::decode_from_bytes(&bytes).unwrap(), person);
+}
+
+#[cfg(feature = "gzip")]
+#[test]
+fn test_corrupt_payload_raises_io_error() {
+    let person = GzipPerson::with_bio(&"likes long walks ".repeat(100));
+
+    let mut bytes = person.encode_to_bytes().unwrap();
+    bytes.truncate(bytes.len() / 2);
+
+    let err = GzipPerson::decode_from_bytes(&bytes).unwrap_err();
+    assert!(err.downcast_ref::<std::io::Error>().is_some());
+}
+
+#[cfg(feature = "gzip")]
+#[test]
+fn test_gzip_decompressed_size_is_limited() {
+    let person = GzipPerson::with_bio(&"likes long walks ".repeat(1000));
+
+    let bytes = person.encode_to_bytes().unwrap();
+    assert!(bytes.len() < 4096);
+
+    let err = GzipPerson::decode_from_bytes(&bytes).unwrap_err();
+    assert_eq!(
+        err.downcast_ref::<ErrDecompressedSizeExceeded>(),
+        Some(&ErrDecompressedSizeExceeded { max: 4096 })
+    );
+}
+
+#[cfg(feature = "zstd")]
+#[test]
+fn test_zstd_decompressed_size_is_limited() {
+    let person = ZstdPerson::with_bio(&"likes long walks ".repeat(1000));
+
+    let bytes = person.encode_to_bytes().unwrap();
+    assert!(bytes.len() < 4096);
+
+    let err = ZstdPerson::decode_from_bytes(&bytes).unwrap_err();
+    assert_eq!(
+        err.downcast_ref::<ErrDecompressedSizeExceeded>(),
+        Some(&ErrDecompressedSizeExceeded { max: 4096 })
+    );
+}
@@ -0,0 +1,135 @@
+#[macro_use]
+mod common;
+
+use cgp::core::error::{ErrorRaiserComponent, ErrorTypeComponent};
+use cgp::prelude::*;
+use cgp_examples::error::{RaiseIntoAnyhow, UseAnyhowError};
+use cgp_examples::format::*;
+use serde::{Deserialize, Serialize};
+
+// Contexts that share the Config fields, but are wired to a different string parser.
+define_contexts! {
+    #[derive(Serialize, Deserialize, Debug)]
+    pub struct {
+        pub name: String,
+        pub port: u16,
+    }
+
+    JsonConfig: JsonConfigComponents {
+        ErrorTypeComponent: UseAnyhowError,
+        ErrorRaiserComponent: RaiseIntoAnyhow,
+        StringParserComponent: ParseFromJsonString,
+    }
+
+    #[cfg(feature = "yaml")]
+    YamlConfig: YamlConfigComponents {
+        ErrorTypeComponent: UseAnyhowError,
+        ErrorRaiserComponent: RaiseIntoAnyhow,
+        StringParserComponent: ParseFromYamlString,
+    }
+
+    #[cfg(feature = "toml")]
+    TomlConfig: TomlConfigComponents {
+        ErrorTypeComponent: UseAnyhowError,
+        ErrorRaiserComponent: RaiseIntoAnyhow,
+        StringParserComponent: ParseFromTomlString,
+    }
+
+    #[cfg(feature = "ron")]
+    RonConfig: RonConfigComponents {
+        ErrorTypeComponent: UseAnyhowError,
+        ErrorRaiserComponent: RaiseIntoAnyhow,
+        StringParserComponent: ParseFromRonString,
+    }
+}
+
+fn diagnostic(err: anyhow::Error) -> ParseDiagnostic {
+    err.downcast::<ParseDiagnostic>().unwrap()
+}
+
+#[test]
+fn test_json_invalid_value_diagnostic() {
+    let raw = "{\n  \"name\": \"api\",\n  \"port\": \"http\"\n}";
+
+    let err = diagnostic(JsonConfig::parse_from_string(raw).unwrap_err());
+
+    assert_eq!(err.format, "JSON");
+    assert_eq!((err.line, err.column, err.offset), (3, 16, 34));
+    assert_eq!(err.expected.as_deref(), Some("u16"));
+    assert_eq!(err.found, "string \"http\"");
+    assert_eq!(
+        err.to_string(),
+        "failed to parse JSON at line 3, column 16: \
+         invalid type: string \"http\", expected u16\n  \
+         |\n3 |   \"port\": \"http\"\n  |                ^"
+    );
+}
+
+#[test]
+fn test_json_syntax_error_diagnostic() {
+    let raw = "{\n  \"name\": \"api\",\n  \"port\": 80,\n}";
+
+    let err = diagnostic(JsonConfig::parse_from_string(raw).unwrap_err());
+
+    assert_eq!((err.line, err.column), (4, 1));
+    assert_eq!(err.message, "trailing comma");
+    assert_eq!(err.found, "`}`");
+}
+
+#[cfg(feature = "yaml")]
+#[test]
+fn test_yaml_diagnostic() {
+    let raw = "name: api\nport: http\n";
+
+    let err = diagnostic(YamlConfig::parse_from_string(raw).unwrap_err());
+
+    assert_eq!(err.format, "YAML");
+    assert_eq!((err.line, err.column), (2, 7));
+    assert_eq!(err.expected.as_deref(), Some("u16"));
+    assert_eq!(err.found, "string \"http\"");
+}
+
+#[cfg(feature = "toml")]
+#[test]
+fn test_toml_diagnostic() {
+    let raw = "name = \"api\"\nport = 99999\n";
+
+    let err = diagnostic(TomlConfig::parse_from_string(raw).unwrap_err());
+
+    assert_eq!(err.format, "TOML");
+    assert_eq!((err.line, err.column), (2, 8));
+    assert!(err.snippet.ends_with("2 | port = 99999\n  |        ^"));
+}
+
+#[cfg(feature = "ron")]
+#[test]
+fn test_ron_diagnostic() {
+    let raw = "(\n    name: \"api\",\n    port: -1,\n)";
+
+    let err = diagnostic(RonConfig::parse_from_string(raw).unwrap_err());
+
+    assert_eq!(err.format, "RON");
+    assert_eq!(err.line, 3);
+}
+
+#[test]
+fn test_snippet_of_long_line_is_windowed() {
+    let raw = format!(r#"{{"name": "{}", "port": x}}"#, "a".repeat(100_000));
+
+    let err = diagnostic(JsonConfig::parse_from_string(&raw).unwrap_err());
+
+    assert_eq!(err.column, 100_022);
+    assert_eq!(err.found, "`x`");
+
+    let mut lines = err.snippet.lines().skip(1);
+    let source_line = lines.next().unwrap();
+    let caret_line = lines.next().unwrap();
+
+    assert!(source_line.starts_with("1 | ...aaa") && source_line.len() < 100);
+    assert_eq!(
+        source_line.find('x'),
+        caret_line.find('^'),
+        "{}",
+        err.snippet
+    );
+}
@@ -0,0 +1,99 @@
+use cgp::core::error::{ErrorRaiserComponent, ErrorTypeComponent};
+use cgp::core::types::impls::WithType;
+use cgp::prelude::*;
+use cgp_examples::auth::{ErrAuthTokenHasExpired, ErrUnknownAuthToken};
+use cgp_examples::error::*;
+
+pub struct App;
+
+pub struct AppComponents;
+
+impl HasComponents for App {
+    type Components = AppComponents;
+}
+
+pub struct AppErrorRaisers;
+
+delegate_components! {
+    AppComponents {
+        ErrorTypeComponent: UseAnyhowError,
+        ErrorRaiserComponent: DispatchErrorRaiser<AppErrorRaisers>,
+        ErrorWrapperComponent: WrapWithAnyhowContext,
+    }
+}
+
+delegate_components! {
+    AppErrorRaisers {
+        ErrAuthTokenHasExpired<u64>: RaiseFrom,
+        serde_json::Error: RaiseIntoAnyhow,
+        [
+            ErrUnknownAuthToken,
+            &'static str,
+        ]: DebugAsAnyhow,
+    }
+}
+
+#[test]
+fn test_dispatch_error_raiser_per_source_error() {
+    let err = App::raise_error(ErrAuthTokenHasExpired {
+        expiry: 1_000u64,
+        now: 1_001,
+    });
+    assert!(err.downcast_ref::<ErrAuthTokenHasExpired<u64>>().is_some());
+
+    let json_err = serde_json::from_str::<u64>("not json").unwrap_err();
+    let err = App::raise_error(json_err);
+    assert!(err.downcast_ref::<serde_json::Error>().is_some());
+
+    let err = App::raise_error(ErrUnknownAuthToken);
+    assert!(err.downcast_ref::<ErrUnknownAuthToken>().is_none());
+    assert_eq!(err.to_string(), "ErrUnknownAuthToken");
+
+    let err = App::raise_error("stringly error");
+    assert_eq!(err.to_string(), "\"stringly error\"");
+}
+
+#[test]
+fn test_wrap_anyhow_error_with_context() {
+    let err = App::raise_error(ErrUnknownAuthToken);
+    let err = App::wrap_error(err, "while fetching expiry for auth token");
+
+    assert_eq!(err.to_string(), "while fetching expiry for auth token");
+    assert_eq!(err.root_cause().to_string(), "ErrUnknownAuthToken");
+}
+
+pub struct DetailedApp;
+
+pub struct DetailedAppComponents;
+
+impl HasComponents for DetailedApp {
+    type Components = DetailedAppComponents;
+}
+
+delegate_components! {
+    DetailedAppComponents {
+        ErrorTypeComponent: WithType<DetailedError<String>>,
+        ErrorRaiserComponent: RaiseDetailedFrom,
+        ErrorWrapperComponent: PushErrorDetail,
+    }
+}
+
+#[test]
+fn test_push_error_detail_chain() {
+    let err = DetailedApp::raise_error("unknown auth token");
+    let err = DetailedApp::wrap_error(err, "while fetching expiry for auth token");
+    let err = DetailedApp::wrap_error(err, "while validating auth token");
+
+    assert_eq!(err.source, "unknown auth token");
+    assert_eq!(
+        err.details,
+        vec![
+            "while fetching expiry for auth token",
+            "while validating auth token"
+        ]
+    );
+    assert_eq!(
+        err.to_string(),
+        "while validating auth token: while fetching expiry for auth token: unknown auth token"
+    );
+}
@@ -0,0 +1,93 @@
+#![cfg(all(feature = "yaml", feature = "toml"))]
+
+use cgp::core::error::{ErrorRaiserComponent, ErrorTypeComponent};
+use cgp::prelude::*;
+use cgp_examples::error::{RaiseIntoAnyhow, UseAnyhowError};
+use cgp_examples::format::*;
+use serde::Deserialize;
+
+#[derive(Deserialize, Debug, Eq, PartialEq)]
+pub struct Person {
+    pub first_name: String,
+    pub last_name: String,
+}
+
+pub struct PersonComponents;
+
+impl HasComponents for Person {
+    type Components = PersonComponents;
+}
+
+delegate_components! {
+    PersonComponents {
+        ErrorTypeComponent: UseAnyhowError,
+        ErrorRaiserComponent: RaiseIntoAnyhow,
+        StringParserComponent:
+            ParseBySniffing<(ParseFromJsonString, ParseFromYamlString, ParseFromTomlString)>,
+    }
+}
+
+type FallbackParser = ParseWithFallback<(
+    ParseFromJsonString,
+    ParseFromYamlString,
+    ParseFromTomlString,
+)>;
+
+fn person() -> Person {
+    Person {
+        first_name: "John".into(),
+        last_name: "Smith".into(),
+    }
+}
+
+const JSON: &str = r#"{"first_name":"John","last_name":"Smith"}"#;
+const YAML: &str = "first_name: John\nlast_name: Smith\n";
+const TOML: &str = "first_name = \"John\"\nlast_name = \"Smith\"\n";
+
+#[test]
+fn test_sniffing_parser_accepts_each_format() {
+    assert_eq!(Person::parse_from_string(JSON).unwrap(), person());
+    assert_eq!(Person::parse_from_string(YAML).unwrap(), person());
+    assert_eq!(Person::parse_from_string(TOML).unwrap(), person());
+}
+
+#[test]
+fn test_fallback_parser_accepts_each_format() {
+    let parse = <FallbackParser as StringParser<Person>>::parse_from_string;
+
+    assert_eq!(parse(JSON).unwrap(), person());
+    assert_eq!(parse(YAML).unwrap(), person());
+    assert_eq!(parse(TOML).unwrap(), person());
+}
+
+#[test]
+fn test_sniffing_tries_the_sniffed_format_first() {
+    assert!(ParseFromTomlString::looks_like(TOML));
+    assert!(!ParseFromYamlString::looks_like(TOML));
+    assert!(ParseFromYamlString::looks_like(YAML));
+    assert!(!ParseFromJsonString::looks_like(YAML));
+}
+
+#[test]
+fn test_all_failures_are_reported() {
+    let err = Person::parse_from_string("first_name John").unwrap_err();
+
+    let failures = &err
+        .downcast_ref::<ErrAllParsersFailed<anyhow::Error>>()
+        .unwrap()
+        .failures;
+
+    let parsers: Vec<&str> = failures
+        .iter()
+        .map(|failure| failure.parser.rsplit("::").next().unwrap())
+        .collect();
+
+    assert_eq!(
+        parsers,
+        vec![
+            "ParseFromJsonString",
+            "ParseFromYamlString",
+            "ParseFromTomlString"
+        ]
+    );
+}
@@ -0,0 +1,178 @@
+#[macro_use]
+mod common;
+
+use cgp::core::error::{ErrorRaiserComponent, ErrorTypeComponent};
+use cgp::core::types::impls::WithType;
+use cgp::prelude::*;
+use cgp_examples::error::RaiseFrom;
+use cgp_examples::format::*;
+use serde::{Deserialize, Serialize};
+
+// Application error enum that does not depend on anyhow
+#[derive(Debug)]
+pub enum AppError {
+    Json(serde_json::Error),
+    Parse(ParseDiagnostic),
+}
+
+impl From<serde_json::Error> for AppError {
+    fn from(e: serde_json::Error) -> Self {
+        Self::Json(e)
+    }
+}
+
+impl From<ParseDiagnostic> for AppError {
+    fn from(e: ParseDiagnostic) -> Self {
+        Self::Parse(e)
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Eq, PartialEq)]
+pub struct Person {
+    pub first_name: String,
+    pub last_name: String,
+}
+
+pub struct PersonComponents;
+
+impl HasComponents for Person {
+    type Components = PersonComponents;
+}
+
+delegate_components! {
+    PersonComponents {
+        ErrorTypeComponent: WithType<AppError>,
+        ErrorRaiserComponent: RaiseFrom,
+        StringFormatterComponent: FormatAsJsonString,
+        StringParserComponent: ParseFromJsonString,
+        WriterComponent: WriteAsJson,
+    }
+}
+
+fn person() -> Person {
+    Person {
+        first_name: "John".into(),
+        last_name: "Smith".into(),
+    }
+}
+
+#[test]
+fn test_json_round_trip_with_app_error() {
+    let person_str = r#"{"first_name":"John","last_name":"Smith"}"#;
+
+    assert_eq!(person().format_to_string().unwrap(), person_str);
+    assert_eq!(Person::parse_from_string(person_str).unwrap(), person());
+}
+
+#[test]
+fn test_json_parse_error_is_raised_into_app_error() {
+    let err = Person::parse_from_string(r#"{"first_name":"John"}"#).unwrap_err();
+
+    assert!(matches!(err, AppError::Parse(_)));
+}
+
+#[test]
+fn test_write_json_into_io_and_fmt_writers() {
+    let person_str = r#"{"first_name":"John","last_name":"Smith"}"#;
+
+    let mut bytes = IoWriter(Vec::new());
+    person().write_formatted(&mut bytes).unwrap();
+    person().write_formatted(&mut bytes).unwrap();
+    assert_eq!(bytes.0, format!("{person_str}{person_str}").as_bytes());
+
+    let mut string = FmtWriter(String::new());
+    person().write_formatted(&mut string).unwrap();
+    assert_eq!(string.0, person_str);
+
+    assert_eq!(
+        FormatWithWriter::<WriteAsJson>::format_to_string(&person()).unwrap(),
+        person_str
+    );
+}
+
+// Contexts that share the Person fields, but are wired to a different string format.
+// Switching formats only takes changing the formatter and parser entries.
+define_contexts! {
+    #[derive(Serialize, Deserialize, Debug, Eq, PartialEq)]
+    pub struct {
+        pub first_name: String,
+        pub last_name: String,
+    }
+
+    impl<Context, Components> {
+        impl Default for Context {
+            fn default() -> Self {
+                Self {
+                    first_name: "John".into(),
+                    last_name: "Smith".into(),
+                }
+            }
+        }
+    }
+
+    #[cfg(feature = "yaml")]
+    YamlPerson: YamlPersonComponents {
+        ErrorTypeComponent: cgp_examples::error::UseAnyhowError,
+        ErrorRaiserComponent: cgp_examples::error::RaiseIntoAnyhow,
+        StringFormatterComponent: FormatAsYamlString,
+        StringParserComponent: ParseFromYamlString,
+    }
+
+    #[cfg(feature = "toml")]
+    TomlPerson: TomlPersonComponents {
+        ErrorTypeComponent: cgp_examples::error::UseAnyhowError,
+        ErrorRaiserComponent: cgp_examples::error::RaiseIntoAnyhow,
+        StringFormatterComponent: FormatAsTomlString,
+        StringParserComponent: ParseFromTomlString,
+    }
+
+    #[cfg(feature = "ron")]
+    RonPerson: RonPersonComponents {
+        ErrorTypeComponent: cgp_examples::error::UseAnyhowError,
+        ErrorRaiserComponent: cgp_examples::error::RaiseIntoAnyhow,
+        StringFormatterComponent: FormatAsRonString,
+        StringParserComponent: ParseFromRonString,
+    }
+}
+
+#[cfg(feature = "yaml")]
+#[test]
+fn test_yaml_round_trip() {
+    let person_str = "first_name: John\nlast_name: Smith\n";
+
+    assert_eq!(
+        YamlPerson::default().format_to_string().unwrap(),
+        person_str
+    );
+    assert_eq!(
+        YamlPerson::parse_from_string(person_str).unwrap(),
+        YamlPerson::default()
+    );
+}
+
+#[cfg(feature = "toml")]
+#[test]
+fn test_toml_round_trip() {
+    let person_str = "first_name = \"John\"\nlast_name = \"Smith\"\n";
+
+    assert_eq!(
+        TomlPerson::default().format_to_string().unwrap(),
+        person_str
+    );
+    assert_eq!(
+        TomlPerson::parse_from_string(person_str).unwrap(),
+        TomlPerson::default()
+    );
+}
+
+#[cfg(feature = "ron")]
+#[test]
+fn test_ron_round_trip() {
+    let person_str = r#"(first_name:"John",last_name:"Smith")"#;
+
+    assert_eq!(RonPerson::default().format_to_string().unwrap(), person_str);
+    assert_eq!(
+        RonPerson::parse_from_string(person_str).unwrap(),
+        RonPerson::default()
+    );
+}
@@ -0,0 +1,303 @@
+// Feeds hostile input to every string parser that is enabled.
+// The parsers that recurse without a depth limit of their own are guarded by ParseWithLimits,
+// and are also checked to never accept input over the parse limits.
+#![cfg(feature = "testing")]
+
+#[macro_use]
+mod common;
+
+#[cfg(feature = "json-schema")]
+use std::sync::OnceLock;
+
+use cgp::core::error::{ErrorRaiserComponent, ErrorTypeComponent};
+use cgp::prelude::*;
+use cgp_examples::error::{RaiseIntoAnyhow, UseAnyhowError};
+use cgp_examples::format::*;
+use cgp_examples::testing::*;
+use proptest::prelude::ProptestConfig;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct Address {
+    pub city: String,
+    pub lines: Vec<String>,
+}
+
+// Contexts that share the Person fields, but are wired to a different string parser.
+define_contexts! {
+    #[derive(Serialize, Deserialize, Debug)]
+    pub struct {
+        pub first_name: String,
+        pub last_name: String,
+        pub age: u8,
+        pub addresses: Vec<Address>,
+    }
+
+    impl<Context, Components> {
+        // Seeds the parsers whose input is easier to format than to write by hand
+        #[allow(dead_code)]
+        impl Context {
+            fn new() -> Self {
+                Self {
+                    first_name: "John".into(),
+                    last_name: "Smith".into(),
+                    age: 42,
+                    addresses: vec![Address {
+                        city: "Springfield".into(),
+                        lines: vec!["742 Evergreen Terrace".into()],
+                    }],
+                }
+            }
+        }
+    }
+
+    JsonPerson: JsonPersonComponents {
+        ErrorTypeComponent: UseAnyhowError,
+        ErrorRaiserComponent: RaiseIntoAnyhow,
+        StringParserComponent: ParseWithLimits<ParseFromJsonString>,
+        ParseLimitsGetterComponent: UseDefaultParseLimits,
+    }
+
+    #[cfg(feature = "yaml")]
+    YamlPerson: YamlPersonComponents {
+        ErrorTypeComponent: UseAnyhowError,
+        ErrorRaiserComponent: RaiseIntoAnyhow,
+        StringParserComponent: ParseWithLimits<ParseFromYamlString>,
+        ParseLimitsGetterComponent: UseDefaultParseLimits,
+    }
+
+    #[cfg(feature = "toml")]
+    TomlPerson: TomlPersonComponents {
+        ErrorTypeComponent: UseAnyhowError,
+        ErrorRaiserComponent: RaiseIntoAnyhow,
+        StringParserComponent: ParseWithLimits<ParseFromTomlString>,
+        ParseLimitsGetterComponent: UseDefaultParseLimits,
+    }
+
+    #[cfg(feature = "ron")]
+    RonPerson: RonPersonComponents {
+        ErrorTypeComponent: UseAnyhowError,
+        ErrorRaiserComponent: RaiseIntoAnyhow,
+        StringParserComponent: ParseWithLimits<ParseFromRonString>,
+        ParseLimitsGetterComponent: UseDefaultParseLimits,
+    }
+
+    VersionedPerson: VersionedPersonComponents {
+        ErrorTypeComponent: UseAnyhowError,
+        ErrorRaiserComponent: RaiseIntoAnyhow,
+        StringFormatterComponent: FormatAsVersionedJson,
+        StringParserComponent: ParseFromVersionedJson,
+        JsonMigratorComponent: MigrationChain<Nil>,
+    }
+
+    #[cfg(feature = "json-schema")]
+    SchemaPerson: SchemaPersonComponents {
+        ErrorTypeComponent: UseAnyhowError,
+        ErrorRaiserComponent: RaiseIntoAnyhow,
+        StringParserComponent: ParseWithJsonSchema<ParseFromJsonString>,
+    }
+
+    #[cfg(all(feature = "yaml", feature = "toml"))]
+    SniffingPerson: SniffingPersonComponents {
+        ErrorTypeComponent: UseAnyhowError,
+        ErrorRaiserComponent: RaiseIntoAnyhow,
+        StringParserComponent: ParseWithLimits<
+            ParseBySniffing<(ParseFromJsonString, ParseFromYamlString, ParseFromTomlString)>,
+        >,
+        ParseLimitsGetterComponent: UseDefaultParseLimits,
+    }
+
+    #[cfg(feature = "signed")]
+    SignedPerson: SignedPersonComponents {
+        ErrorTypeComponent: UseAnyhowError,
+        ErrorRaiserComponent: RaiseIntoAnyhow,
+        StringFormatterComponent: FormatSigned<FormatAsJsonString>,
+        StringParserComponent: ParseVerified<ParseFromJsonString>,
+    }
+
+    // Declares parse limits, but its parser is not wrapped in ParseWithLimits
+    UnguardedPerson: UnguardedPersonComponents {
+        ErrorTypeComponent: UseAnyhowError,
+        ErrorRaiserComponent: RaiseIntoAnyhow,
+        StringParserComponent: ParseFromJsonString,
+    }
+
+    FragilePerson: FragilePersonComponents {
+        ErrorTypeComponent: UseAnyhowError,
+        ErrorRaiserComponent: RaiseIntoAnyhow,
+        StringParserComponent: ParseAfterFirstChar,
+    }
+
+    #[cfg(feature = "base64")]
+    EncodedPerson: EncodedPersonComponents {
+        ErrorTypeComponent: UseAnyhowError,
+        ErrorRaiserComponent: RaiseIntoAnyhow,
+        StringFormatterComponent: FormatAsEncodedString<EncodeAsUtf8<FormatAsJsonString>, Base64>,
+        StringParserComponent: ParseFromEncodedString<DecodeFromUtf8<ParseFromJsonString>, Base64>,
+    }
+}
+
+impl ParseLimitsGetter<UnguardedPerson> for UnguardedPersonComponents {
+    fn parse_limits() -> ParseLimits {
+        ParseLimits {
+            max_depth: 2,
+            ..ParseLimits::default()
+        }
+    }
+}
+
+// Skips the first character of the input by slicing it, which panics on empty input
+// or input that starts with a multi-byte character.
+pub struct ParseAfterFirstChar;
+
+impl<Context> StringParser<Context> for ParseAfterFirstChar
+where
+    Context: HasErrorType,
+    ParseFromJsonString: StringParser<Context>,
+{
+    fn parse_from_string(raw: &str) -> Result<Context, Context::Error> {
+        ParseFromJsonString::parse_from_string(&raw[1..])
+    }
+}
+
+impl SchemaVersionGetter<VersionedPerson> for VersionedPersonComponents {
+    fn schema_version() -> u64 {
+        2
+    }
+}
+
+#[cfg(feature = "json-schema")]
+impl JsonSchemaGetter<SchemaPerson> for SchemaPersonComponents {
+    fn json_schema() -> &'static JsonSchema {
+        static SCHEMA: OnceLock<JsonSchema> = OnceLock::new();
+
+        SCHEMA.get_or_init(|| {
+            JsonSchema::new(serde_json::json!({
+                "type": "object",
+                "properties": {
+                    "first_name": { "type": "string", "minLength": 1 },
+                    "addresses": { "type": "array", "maxItems": 4 },
+                },
+                "required": ["first_name", "last_name"],
+            }))
+        })
+    }
+}
+
+#[cfg(feature = "signed")]
+impl SigningKeyGetter<SignedPerson> for SignedPersonComponents {
+    fn signing_key() -> &'static [u8] {
+        b"key"
+    }
+}
+
+const JSON: &str = r#"{"first_name":"John","last_name":"Smith","age":42,"addresses":[{"city":"Springfield","lines":["742 Evergreen Terrace"]}]}"#;
+
+#[test]
+fn test_json_parser_is_robust() {
+    let seeds = [JSON, "[]"];
+
+    assert_parser_is_robust::<JsonPerson>(&seeds);
+    assert_parser_respects_limits::<JsonPerson>(&seeds);
+}
+
+#[cfg(feature = "yaml")]
+#[test]
+fn test_yaml_parser_is_robust() {
+    let seeds = [
+        "first_name: John\nlast_name: Smith\nage: 42\naddresses:\n- city: Springfield\n  lines:\n  - 742 Evergreen Terrace\n",
+        "{first_name: John, addresses: [{lines: [a, b]}]}",
+    ];
+
+    assert_parser_is_robust::<YamlPerson>(&seeds);
+    assert_parser_respects_limits::<YamlPerson>(&seeds);
+}
+
+#[cfg(feature = "toml")]
+#[test]
+fn test_toml_parser_is_robust() {
+    let seeds = [
+        "first_name = \"John\"\nlast_name = \"Smith\"\nage = 42\n\n[[addresses]]\ncity = \"Springfield\"\nlines = [\"742 Evergreen Terrace\"]\n",
+        "addresses = [{ city = \"a\", lines = [] }]",
+    ];
+
+    assert_parser_is_robust::<TomlPerson>(&seeds);
+    assert_parser_respects_limits::<TomlPerson>(&seeds);
+}
+
+#[cfg(feature = "ron")]
+#[test]
+fn test_ron_parser_is_robust() {
+    let seeds = [
+        r#"(first_name:"John",last_name:"Smith",age:42,addresses:[(city:"Springfield",lines:["742 Evergreen Terrace"])])"#,
+    ];
+
+    assert_parser_is_robust::<RonPerson>(&seeds);
+    assert_parser_respects_limits::<RonPerson>(&seeds);
+}
+
+#[test]
+fn test_versioned_json_parser_is_robust() {
+    let versioned = VersionedPerson::new().format_to_string().unwrap();
+
+    assert_parser_is_robust::<VersionedPerson>(&[&versioned, JSON, r#"{"version":1,"data":{}}"#]);
+}
+
+#[cfg(feature = "json-schema")]
+#[test]
+fn test_json_schema_parser_is_robust() {
+    assert_parser_is_robust::<SchemaPerson>(&[
+        JSON,
+        r#"{"first_name":"","addresses":[1,2,3,4,5]}"#,
+    ]);
+}
+
+#[cfg(all(feature = "yaml", feature = "toml"))]
+#[test]
+fn test_sniffing_parser_is_robust() {
+    let seeds = [
+        JSON,
+        "first_name: John\nlast_name: Smith\nage: 42\naddresses: []\n",
+        "first_name = \"John\"\nlast_name = \"Smith\"\nage = 42\naddresses = []\n",
+    ];
+
+    assert_parser_is_robust::<SniffingPerson>(&seeds);
+    assert_parser_respects_limits::<SniffingPerson>(&seeds);
+}
+
+#[cfg(feature = "signed")]
+#[test]
+fn test_verified_parser_is_robust() {
+    let signed = SignedPerson::new().format_to_string().unwrap();
+
+    assert_parser_is_robust::<SignedPerson>(&[&signed]);
+}
+
+#[cfg(feature = "base64")]
+#[test]
+fn test_encoded_string_parser_is_robust() {
+    let encoded = EncodedPerson::new().format_to_string().unwrap();
+
+    assert_parser_is_robust::<EncodedPerson>(&[&encoded]);
+}
+
+#[test]
+#[should_panic(expected = "parser of fuzz::FragilePerson is not robust")]
+fn test_panicking_parser_is_caught() {
+    assert_parser_is_robust_with_config::<FragilePerson>(&[JSON], ProptestConfig::with_cases(64));
+}
+
+#[test]
+#[should_panic(expected = "parser of fuzz::UnguardedPerson does not respect its parse limits")]
+fn test_unguarded_parser_is_caught() {
+    assert_parser_respects_limits_with_config::<UnguardedPerson>(
+        &[JSON],
+        ProptestConfig::with_cases(64),
+    );
+}
+
+#[test]
+#[should_panic(expected = "at least one seed is required")]
+fn test_seeds_are_required() {
+    assert_parser_is_robust::<JsonPerson>(&[]);
+}
@@ -0,0 +1,90 @@
+#![cfg(feature = "json-schema")]
+
+use std::sync::OnceLock;
+
+use cgp::core::error::{ErrorRaiserComponent, ErrorTypeComponent};
+use cgp::prelude::*;
+use cgp_examples::error::{RaiseIntoAnyhow, UseAnyhowError};
+use cgp_examples::format::*;
+use serde::Deserialize;
+use serde_json::json;
+
+#[derive(Deserialize, Debug, Eq, PartialEq)]
+pub struct Person {
+    pub first_name: String,
+    pub middle_name: Option<String>,
+    pub last_name: String,
+}
+
+pub struct PersonComponents;
+
+impl HasComponents for Person {
+    type Components = PersonComponents;
+}
+
+delegate_components! {
+    PersonComponents {
+        ErrorTypeComponent: UseAnyhowError,
+        ErrorRaiserComponent: RaiseIntoAnyhow,
+        StringParserComponent: ParseWithJsonSchema<ParseFromJsonString>,
+    }
+}
+
+impl JsonSchemaGetter<Person> for PersonComponents {
+    fn json_schema() -> &'static JsonSchema {
+        static SCHEMA: OnceLock<JsonSchema> = OnceLock::new();
+
+        SCHEMA.get_or_init(|| {
+            JsonSchema::new(json!({
+                "type": "object",
+                "properties": {
+                    "first_name": { "type": "string", "minLength": 1 },
+                    "middle_name": { "type": ["string", "null"] },
+                    "last_name": { "type": "string", "pattern": "^[A-Z]" },
+                },
+                "required": ["first_name", "middle_name", "last_name"],
+            }))
+        })
+    }
+}
+
+#[test]
+fn test_valid_input_is_parsed() {
+    let person = Person::parse_from_string(
+        r#"{"first_name":"John","middle_name":null,"last_name":"Smith"}"#,
+    )
+    .unwrap();
+
+    assert_eq!(
+        person,
+        Person {
+            first_name: "John".into(),
+            middle_name: None,
+            last_name: "Smith".into(),
+        }
+    );
+}
+
+#[test]
+fn test_all_violations_are_reported_with_pointers() {
+    let err = Person::parse_from_string(r#"{"first_name":"","last_name":"smith"}"#).unwrap_err();
+
+    let violations = &err
+        .downcast_ref::<ErrJsonSchemaViolations>()
+        .unwrap()
+        .violations;
+
+    let mut pointers: Vec<&str> = violations.iter().map(|v| v.pointer.as_str()).collect();
+    pointers.sort();
+
+    // The missing middle_name is reported against the enclosing object
+    assert_eq!(pointers, vec!["", "/first_name", "/last_name"]);
+}
+
+#[test]
+fn test_invalid_json_is_raised_before_validation() {
+    let err = Person::parse_from_string("{").unwrap_err();
+
+    let err = err.downcast_ref::<ParseDiagnostic>().unwrap();
+    assert_eq!((err.line, err.column), (1, 1));
+}
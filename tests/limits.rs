@@ -0,0 +1,79 @@
+use cgp::core::error::{ErrorRaiserComponent, ErrorTypeComponent};
+use cgp::prelude::*;
+use cgp_examples::error::{RaiseIntoAnyhow, UseAnyhowError};
+use cgp_examples::format::*;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+pub struct Document {
+    pub title: String,
+    pub body: Value,
+}
+
+pub struct DocumentComponents;
+
+impl HasComponents for Document {
+    type Components = DocumentComponents;
+}
+
+delegate_components! {
+    DocumentComponents {
+        ErrorTypeComponent: UseAnyhowError,
+        ErrorRaiserComponent: RaiseIntoAnyhow,
+        StringParserComponent: ParseWithLimits<ParseFromJsonString>,
+    }
+}
+
+impl ParseLimitsGetter<Document> for DocumentComponents {
+    fn parse_limits() -> ParseLimits {
+        ParseLimits {
+            max_input_len: 64,
+            max_depth: 3,
+            max_string_len: 8,
+        }
+    }
+}
+
+fn limit_exceeded(raw: &str) -> ErrParseLimitExceeded {
+    Document::parse_from_string(raw)
+        .unwrap_err()
+        .downcast::<ErrParseLimitExceeded>()
+        .unwrap()
+}
+
+#[test]
+fn test_input_within_limits_is_parsed() {
+    let document = Document::parse_from_string(r#"{"title":"limits","body":[[1]]}"#).unwrap();
+
+    assert_eq!(document.body, serde_json::json!([[1]]));
+}
+
+#[test]
+fn test_input_over_limits_is_rejected() {
+    let err = limit_exceeded(&format!(r#"{{"title":"{}"}}"#, " ".repeat(64)));
+    assert_eq!(err.limit, ParseLimit::InputLength);
+
+    let err = limit_exceeded(r#"{"title":"limits","body":[[[1]]]}"#);
+    assert_eq!(
+        err,
+        ErrParseLimitExceeded {
+            limit: ParseLimit::Depth,
+            max: 3,
+            offset: 27,
+        }
+    );
+
+    let err = limit_exceeded(r#"{"title":"too long a title"}"#);
+    assert_eq!(
+        err.to_string(),
+        "string length exceeds the limit of 8 at offset 9"
+    );
+}
+
+#[test]
+fn test_brackets_in_strings_are_not_nesting() {
+    let document = Document::parse_from_string(r#"{"title":"[[[{\"","body":1}"#).unwrap();
+
+    assert_eq!(document.title, "[[[{\"");
+}
@@ -0,0 +1,118 @@
+use cgp::core::error::{ErrorRaiserComponent, ErrorTypeComponent};
+use cgp::prelude::*;
+use cgp_examples::error::{RaiseIntoAnyhow, UseAnyhowError};
+use cgp_examples::format::*;
+use serde::Serialize;
+
+// Stands in for a domain crate that derives neither Serialize nor Display,
+// and only exposes its fields through HasField.
+mod domain {
+    use cgp::prelude::*;
+
+    #[derive(HasField)]
+    pub struct Person {
+        pub first_name: String,
+        pub last_name: String,
+        pub age: u8,
+    }
+}
+
+use domain::Person;
+
+pub struct PersonComponents;
+
+impl HasComponents for Person {
+    type Components = PersonComponents;
+}
+
+delegate_components! {
+    PersonComponents {
+        ErrorTypeComponent: UseAnyhowError,
+        ErrorRaiserComponent: RaiseIntoAnyhow,
+        SerializeComponent: SerializeFields<Product![symbol!("first_name"), symbol!("age")]>,
+        FormatComponent: DisplayFields<Product![symbol!("first_name"), symbol!("last_name")]>,
+        StringFormatterComponent: FormatProvidedAsJsonString,
+    }
+}
+
+// A type that does implement the serde and std traits, and is wired to use them.
+#[derive(Serialize, Debug)]
+pub struct Point {
+    pub x: i32,
+    pub y: i32,
+}
+
+pub struct PointComponents;
+
+impl HasComponents for Point {
+    type Components = PointComponents;
+}
+
+delegate_components! {
+    PointComponents {
+        ErrorTypeComponent: UseAnyhowError,
+        ErrorRaiserComponent: RaiseIntoAnyhow,
+        SerializeComponent: SerializeWithSerde,
+        FormatComponent: UseDebug,
+        StringFormatterComponent: FormatProvidedString,
+    }
+}
+
+fn person() -> Person {
+    Person {
+        first_name: "John".into(),
+        last_name: "Smith".into(),
+        age: 42,
+    }
+}
+
+#[test]
+fn test_serialize_and_display_through_field_providers() {
+    let person = person();
+
+    assert_eq!(
+        serde_json::to_string(&Provided(&person)).unwrap(),
+        r#"{"first_name":"John","age":42}"#
+    );
+    assert_eq!(Provided(&person).to_string(), "John Smith");
+}
+
+#[test]
+fn test_string_formatters_use_wired_providers() {
+    assert_eq!(
+        person().format_to_string().unwrap(),
+        r#"{"first_name":"John","age":42}"#
+    );
+
+    let point = Point { x: 1, y: -2 };
+    assert_eq!(point.format_to_string().unwrap(), "Point { x: 1, y: -2 }");
+    assert_eq!(
+        serde_json::to_string(&Provided(&point)).unwrap(),
+        r#"{"x":1,"y":-2}"#
+    );
+}
+
+#[test]
+fn test_provided_methods_do_not_clash_with_serde() {
+    let point = Point { x: 1, y: -2 };
+
+    let mut json = Vec::new();
+    point
+        .serialize_provided(&mut serde_json::Serializer::new(&mut json))
+        .unwrap();
+    point
+        .serialize(&mut serde_json::Serializer::new(&mut json))
+        .unwrap();
+
+    assert_eq!(json, br#"{"x":1,"y":-2}{"x":1,"y":-2}"#);
+}
+
+#[test]
+fn test_provided_nests_inside_serde_types() {
+    let (john, jane) = (person(), person());
+
+    assert_eq!(
+        serde_json::to_string(&vec![Provided(&john), Provided(&jane)]).unwrap(),
+        r#"[{"first_name":"John","age":42},{"first_name":"John","age":42}]"#
+    );
+}
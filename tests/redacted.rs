@@ -0,0 +1,184 @@
+use core::fmt::{self, Debug};
+use std::collections::BTreeMap;
+
+use cgp::core::error::{ErrorRaiserComponent, ErrorTypeComponent};
+use cgp::prelude::*;
+use cgp_examples::error::{RaiseIntoAnyhow, UseAnyhowError};
+use cgp_examples::format::*;
+use serde::Serialize;
+
+#[derive(Serialize, Debug)]
+pub struct Person {
+    pub first_name: String,
+    pub last_name: String,
+    pub auth_tokens_store: BTreeMap<String, u64>,
+    pub aliases: Vec<String>,
+}
+
+pub struct PersonComponents;
+
+impl HasComponents for Person {
+    type Components = PersonComponents;
+}
+
+delegate_components! {
+    PersonComponents {
+        ErrorTypeComponent: UseAnyhowError,
+        ErrorRaiserComponent: RaiseIntoAnyhow,
+    }
+}
+
+impl RedactedFieldsGetter<Person> for PersonComponents {
+    fn redacted_fields() -> &'static [&'static str] {
+        &["last_name", "auth_tokens_store.*", "aliases.1"]
+    }
+}
+
+fn person() -> Person {
+    Person {
+        first_name: "John".into(),
+        last_name: "Smith".into(),
+        auth_tokens_store: BTreeMap::from([("abc".into(), 1000), ("x,y: z".into(), 2000)]),
+        aliases: vec!["Johnny".into(), "J. Smith".into()],
+    }
+}
+
+#[test]
+fn test_redact_json_output() {
+    let output = <FormatRedacted<FormatAsJsonString> as StringFormatter<Person>>::format_to_string(
+        &person(),
+    )
+    .unwrap();
+
+    assert_eq!(
+        output,
+        r#"{"first_name":"John","last_name":"[REDACTED]","auth_tokens_store":{"abc":"[REDACTED]","x,y: z":"[REDACTED]"},"aliases":["Johnny","[REDACTED]"]}"#
+    );
+}
+
+#[test]
+fn test_redact_prettified_json_output() {
+    let output = <FormatRedacted<FormatAsPrettifiedJsonString> as StringFormatter<Person>>::format_to_string(
+        &person(),
+    )
+    .unwrap();
+
+    assert!(output.contains("\n  \"last_name\": \"[REDACTED]\""));
+    assert!(!output.contains("Smith\""));
+}
+
+#[test]
+fn test_redact_debug_output() {
+    let output =
+        <FormatRedacted<FormatStringWithDebug> as StringFormatter<Person>>::format_to_string(
+            &person(),
+        )
+        .unwrap();
+
+    assert_eq!(
+        output,
+        r#"Person { first_name: "John", last_name: [REDACTED], auth_tokens_store: {"abc": [REDACTED], "x,y: z": [REDACTED]}, aliases: ["Johnny", [REDACTED]] }"#
+    );
+}
+
+#[cfg(feature = "yaml")]
+#[test]
+fn test_redact_yaml_output() {
+    let output = <FormatRedacted<FormatAsYamlString> as StringFormatter<Person>>::format_to_string(
+        &person(),
+    )
+    .unwrap();
+
+    assert_eq!(
+        output,
+        "first_name: John\nlast_name: '[REDACTED]'\nauth_tokens_store:\n  abc: '[REDACTED]'\n  'x,y: z': '[REDACTED]'\naliases:\n- Johnny\n- '[REDACTED]'\n"
+    );
+}
+
+#[cfg(feature = "toml")]
+#[test]
+fn test_redact_toml_output() {
+    let output = <FormatRedacted<FormatAsTomlString> as StringFormatter<Person>>::format_to_string(
+        &person(),
+    )
+    .unwrap();
+
+    assert_eq!(
+        output,
+        "first_name = \"John\"\nlast_name = \"[REDACTED]\"\naliases = [\"Johnny\", \"[REDACTED]\"]\n\n[auth_tokens_store]\nabc = \"[REDACTED]\"\n\"x,y: z\" = \"[REDACTED]\"\n"
+    );
+}
+
+#[cfg(feature = "ron")]
+#[test]
+fn test_redact_ron_output() {
+    let output =
+        <FormatRedacted<FormatAsRonString> as StringFormatter<Person>>::format_to_string(&person())
+            .unwrap();
+
+    // Structs come out as maps keyed by their field names
+    assert_eq!(
+        output,
+        r#"{"first_name":"John","last_name":"[REDACTED]","auth_tokens_store":{"abc":"[REDACTED]","x,y: z":"[REDACTED]"},"aliases":["Johnny","[REDACTED]"]}"#
+    );
+}
+
+// Prints its text as is, which does not have to be valid Debug syntax.
+pub struct Tag(pub &'static str);
+
+impl Debug for Tag {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.0)
+    }
+}
+
+#[derive(Debug)]
+pub struct TaggedPerson {
+    pub tag: Tag,
+    pub last_name: String,
+}
+
+pub struct TaggedPersonComponents;
+
+impl HasComponents for TaggedPerson {
+    type Components = TaggedPersonComponents;
+}
+
+delegate_components! {
+    TaggedPersonComponents {
+        ErrorTypeComponent: UseAnyhowError,
+        ErrorRaiserComponent: RaiseIntoAnyhow,
+    }
+}
+
+impl RedactedFieldsGetter<TaggedPerson> for TaggedPersonComponents {
+    fn redacted_fields() -> &'static [&'static str] {
+        &["last_name"]
+    }
+}
+
+fn format_tagged_person(tag: &'static str) -> String {
+    let person = TaggedPerson {
+        tag: Tag(tag),
+        last_name: "Smith".into(),
+    };
+
+    <FormatRedacted<FormatStringWithDebug> as StringFormatter<TaggedPerson>>::format_to_string(
+        &person,
+    )
+    .unwrap()
+}
+
+#[test]
+fn test_redact_unbalanced_debug_output() {
+    assert_eq!(format_tagged_person("retry in 5s)"), "[REDACTED]");
+    assert_eq!(format_tagged_person("retry in (5s"), "[REDACTED]");
+}
+
+#[test]
+fn test_redact_rest_of_debug_output_after_losing_track() {
+    assert_eq!(
+        format_tagged_person("retry }, in: { 5s"),
+        "TaggedPerson { tag: retry }[REDACTED]"
+    );
+}
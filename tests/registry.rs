@@ -0,0 +1,131 @@
+use core::fmt::{self, Display};
+
+use cgp::core::error::{ErrorRaiserComponent, ErrorTypeComponent};
+use cgp::prelude::*;
+use cgp_examples::error::{RaiseIntoAnyhow, UseAnyhowError};
+use cgp_examples::format::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Debug, Eq, PartialEq)]
+pub struct Person {
+    pub first_name: String,
+    pub last_name: String,
+}
+
+impl Display for Person {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.first_name, self.last_name)
+    }
+}
+
+pub struct PersonComponents;
+
+impl HasComponents for Person {
+    type Components = PersonComponents;
+}
+
+delegate_components! {
+    PersonComponents {
+        ErrorTypeComponent: UseAnyhowError,
+        ErrorRaiserComponent: RaiseIntoAnyhow,
+    }
+}
+
+fn person() -> Person {
+    Person {
+        first_name: "John".into(),
+        last_name: "Smith".into(),
+    }
+}
+
+fn registry() -> FormatRegistry<Person> {
+    FormatRegistry::new()
+        .with_formatter::<FormatAsJsonString>("application/json")
+        .with_formatter::<FormatAsPrettifiedJsonString>("application/vnd.pretty+json")
+        .with_formatter::<FormatStringWithDisplay>("text/plain")
+        .with_formatter::<FormatStringWithDebug>("text/x-debug")
+        .with_parser::<ParseFromJsonString>("application/json")
+}
+
+#[test]
+fn test_format_as_accepted_media_type() {
+    let registry = registry();
+
+    assert_eq!(
+        registry.format_as(&person(), "application/json").unwrap(),
+        r#"{"first_name":"John","last_name":"Smith"}"#
+    );
+    assert_eq!(
+        registry.format_as(&person(), "text/plain").unwrap(),
+        "John Smith"
+    );
+    assert_eq!(
+        registry.format_as(&person(), "text/x-debug").unwrap(),
+        r#"Person { first_name: "John", last_name: "Smith" }"#
+    );
+    assert!(registry
+        .format_as(&person(), "application/vnd.pretty+json")
+        .unwrap()
+        .contains('\n'));
+}
+
+#[test]
+fn test_negotiate_accept_header() {
+    let registry = registry();
+
+    assert_eq!(
+        registry.negotiate("text/html, text/plain;q=0.5, application/json;q=0.9"),
+        Some("application/json")
+    );
+    assert_eq!(registry.negotiate("text/*"), Some("text/plain"));
+    assert_eq!(registry.negotiate("*/*"), Some("application/json"));
+    assert_eq!(registry.negotiate("application/json;q=0"), None);
+
+    // The most specific matching range sets the q-value, even when it is lower
+    let registry = FormatRegistry::<Person>::new()
+        .with_formatter::<FormatAsJsonString>("application/json")
+        .with_formatter::<FormatStringWithDisplay>("text/plain");
+
+    assert_eq!(
+        registry.negotiate("*/*, application/json;q=0"),
+        Some("text/plain")
+    );
+    assert_eq!(
+        registry.negotiate("application/json;q=0.1, */*;q=0.9"),
+        Some("text/plain")
+    );
+    assert_eq!(
+        registry.negotiate("text/plain, */*;q=0.5"),
+        Some("text/plain")
+    );
+}
+
+#[test]
+fn test_parse_as_content_type() {
+    let registry = registry();
+
+    let person_str = r#"{"first_name":"John","last_name":"Smith"}"#;
+
+    assert_eq!(
+        registry
+            .parse_as(person_str, "Application/JSON; charset=utf-8")
+            .unwrap(),
+        person()
+    );
+}
+
+#[test]
+fn test_unsupported_media_type_is_raised() {
+    let registry = registry();
+
+    let err = registry.format_as(&person(), "text/html").unwrap_err();
+    let err = err.downcast_ref::<ErrUnsupportedMediaType>().unwrap();
+    assert_eq!(err.media_type, "text/html");
+    assert_eq!(err.supported.len(), 4);
+
+    let err = registry.parse_as("", "text/plain").unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "unsupported media type `text/plain`, expected one of: application/json"
+    );
+}
@@ -0,0 +1,131 @@
+// Checks the round trip of every string format that is enabled over generated contexts
+#![cfg(feature = "testing")]
+
+#[macro_use]
+mod common;
+
+use core::fmt::{self, Display};
+
+use cgp::core::error::{ErrorRaiserComponent, ErrorTypeComponent};
+use cgp::prelude::*;
+use cgp_examples::error::{RaiseIntoAnyhow, UseAnyhowError};
+use cgp_examples::format::*;
+use cgp_examples::testing::*;
+use proptest::prelude::*;
+use serde::{Deserialize, Serialize};
+
+// Contexts that share the Person fields, but are wired to a different formatter and parser.
+define_contexts! {
+    #[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+    pub struct {
+        pub first_name: String,
+        pub last_name: String,
+        pub age: u8,
+        pub nickname: Option<String>,
+    }
+
+    impl<Context, Components> {
+        impl Arbitrary for Context {
+            type Parameters = ();
+            type Strategy = BoxedStrategy<Self>;
+
+            fn arbitrary_with(_: ()) -> Self::Strategy {
+                (
+                    any::<String>(),
+                    any::<String>(),
+                    any::<u8>(),
+                    any::<Option<String>>(),
+                )
+                    .prop_map(|(first_name, last_name, age, nickname)| Self {
+                        first_name,
+                        last_name,
+                        age,
+                        nickname,
+                    })
+                    .boxed()
+            }
+        }
+
+        impl Display for Context {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                write!(f, "{} {}", self.first_name, self.last_name)
+            }
+        }
+    }
+
+    JsonPerson: JsonPersonComponents {
+        ErrorTypeComponent: UseAnyhowError,
+        ErrorRaiserComponent: RaiseIntoAnyhow,
+        StringFormatterComponent: FormatAsJsonString,
+        StringParserComponent: ParseFromJsonString,
+    }
+
+    PrettyJsonPerson: PrettyJsonPersonComponents {
+        ErrorTypeComponent: UseAnyhowError,
+        ErrorRaiserComponent: RaiseIntoAnyhow,
+        StringFormatterComponent: FormatAsPrettifiedJsonString,
+        StringParserComponent: ParseFromJsonString,
+    }
+
+    // Display drops the age, so its output cannot be parsed back
+    LossyPerson: LossyPersonComponents {
+        ErrorTypeComponent: UseAnyhowError,
+        ErrorRaiserComponent: RaiseIntoAnyhow,
+        StringFormatterComponent: FormatStringWithDisplay,
+        StringParserComponent: ParseFromJsonString,
+    }
+
+    #[cfg(feature = "yaml")]
+    YamlPerson: YamlPersonComponents {
+        ErrorTypeComponent: UseAnyhowError,
+        ErrorRaiserComponent: RaiseIntoAnyhow,
+        StringFormatterComponent: FormatAsYamlString,
+        StringParserComponent: ParseFromYamlString,
+    }
+
+    #[cfg(feature = "toml")]
+    TomlPerson: TomlPersonComponents {
+        ErrorTypeComponent: UseAnyhowError,
+        ErrorRaiserComponent: RaiseIntoAnyhow,
+        StringFormatterComponent: FormatAsTomlString,
+        StringParserComponent: ParseFromTomlString,
+    }
+
+    #[cfg(feature = "ron")]
+    RonPerson: RonPersonComponents {
+        ErrorTypeComponent: UseAnyhowError,
+        ErrorRaiserComponent: RaiseIntoAnyhow,
+        StringFormatterComponent: FormatAsRonString,
+        StringParserComponent: ParseFromRonString,
+    }
+}
+
+#[test]
+fn test_json_roundtrip() {
+    assert_roundtrip::<JsonPerson>();
+    assert_roundtrip::<PrettyJsonPerson>();
+}
+
+#[test]
+#[should_panic(expected = "round trip of roundtrip::LossyPerson failed")]
+fn test_lossy_roundtrip_fails() {
+    assert_roundtrip_with_config::<LossyPerson>(ProptestConfig::with_cases(16));
+}
+
+#[cfg(feature = "yaml")]
+#[test]
+fn test_yaml_roundtrip() {
+    assert_roundtrip::<YamlPerson>();
+}
+
+#[cfg(feature = "toml")]
+#[test]
+fn test_toml_roundtrip() {
+    assert_roundtrip::<TomlPerson>();
+}
+
+#[cfg(feature = "ron")]
+#[test]
+fn test_ron_roundtrip() {
+    assert_roundtrip::<RonPerson>();
+}
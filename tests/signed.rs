@@ -0,0 +1,103 @@
+#![cfg(feature = "signed")]
+
+use cgp::core::error::{ErrorRaiserComponent, ErrorTypeComponent};
+use cgp::prelude::*;
+use cgp_examples::error::{RaiseIntoAnyhow, UseAnyhowError};
+use cgp_examples::format::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Debug, Eq, PartialEq)]
+pub struct Person {
+    pub first_name: String,
+    pub last_name: String,
+}
+
+pub struct PersonComponents;
+
+impl HasComponents for Person {
+    type Components = PersonComponents;
+}
+
+delegate_components! {
+    PersonComponents {
+        ErrorTypeComponent: UseAnyhowError,
+        ErrorRaiserComponent: RaiseIntoAnyhow,
+        StringFormatterComponent: FormatSigned<FormatAsJsonString>,
+        StringParserComponent: ParseVerified<ParseFromJsonString>,
+    }
+}
+
+impl SigningKeyGetter<Person> for PersonComponents {
+    fn signing_key() -> &'static [u8] {
+        b"key"
+    }
+}
+
+fn person() -> Person {
+    Person {
+        first_name: "John".into(),
+        last_name: "Smith".into(),
+    }
+}
+
+#[test]
+fn test_signed_roundtrip() {
+    let signed = person().format_to_string().unwrap();
+
+    let (payload, tag) = signed.rsplit_once('.').unwrap();
+    assert_eq!(payload, r#"{"first_name":"John","last_name":"Smith"}"#);
+    assert_eq!(tag.len(), 64);
+
+    assert_eq!(Person::parse_from_string(&signed).unwrap(), person());
+}
+
+// Test vector of the HMAC-SHA256 Wikipedia article
+#[test]
+fn test_tag_matches_known_hmac() {
+    let signed = "The quick brown fox jumps over the lazy dog.\
+                  f7bc83f430538424b13298e6aa6fb143ef4d59a14946175997479dbc2d1a3cd8";
+
+    let err = Person::parse_from_string(signed).unwrap_err();
+
+    // The tag is valid, so the payload makes it through to the JSON parser
+    assert!(err.downcast_ref::<ParseDiagnostic>().is_some());
+}
+
+#[test]
+fn test_tampered_payload_is_rejected() {
+    let signed = person().format_to_string().unwrap();
+    let tampered = signed.replace("Smith", "Smyth");
+
+    let err = Person::parse_from_string(&tampered).unwrap_err();
+    assert!(err.downcast_ref::<ErrInvalidSignature>().is_some());
+}
+
+#[test]
+fn test_malformed_signature_is_rejected() {
+    let signed = person().format_to_string().unwrap();
+
+    for raw in [
+        r#"{"first_name":"John","last_name":"Smith"}"#,
+        &signed[..signed.len() - 1],
+        &signed.replace(&signed[signed.len() - 2..], "zz"),
+    ] {
+        let err = Person::parse_from_string(raw).unwrap_err();
+        assert!(err.downcast_ref::<ErrMalformedSignature>().is_some());
+    }
+}
+
+#[test]
+fn test_plus_sign_in_signature_is_rejected() {
+    let signed = person().format_to_string().unwrap();
+    let (payload, tag) = signed.rsplit_once('.').unwrap();
+
+    // "+a" would parse as the byte "0a" with u8::from_str_radix
+    let index = (0..tag.len())
+        .step_by(2)
+        .find(|&index| tag.as_bytes()[index] == b'0')
+        .unwrap();
+    let raw = format!("{payload}.{}+{}", &tag[..index], &tag[index + 1..]);
+
+    let err = Person::parse_from_string(&raw).unwrap_err();
+    assert!(err.downcast_ref::<ErrMalformedSignature>().is_some());
+}
@@ -0,0 +1,118 @@
+#[macro_use]
+mod common;
+
+use cgp::core::error::{ErrorRaiserComponent, ErrorTypeComponent};
+use cgp::prelude::*;
+use cgp_examples::error::{RaiseIntoAnyhow, UseAnyhowError};
+use cgp_examples::format::*;
+use serde::Serialize;
+
+#[derive(Serialize)]
+pub struct Address {
+    pub city: String,
+}
+
+// Contexts that share the Person fields, but are rendered with a different template.
+define_contexts! {
+    #[derive(Serialize)]
+    pub struct {
+        pub first_name: String,
+        pub last_name: String,
+        pub age: u8,
+        pub nickname: Option<String>,
+        pub address: Address,
+    }
+
+    impl<Context, Components> {
+        impl Context {
+            fn new() -> Self {
+                Self {
+                    first_name: "John".into(),
+                    last_name: "Smith".into(),
+                    age: 42,
+                    nickname: None,
+                    address: Address {
+                        city: "Springfield".into(),
+                    },
+                }
+            }
+        }
+    }
+
+    Person: PersonComponents {
+        ErrorTypeComponent: UseAnyhowError,
+        ErrorRaiserComponent: RaiseIntoAnyhow,
+        StringFormatterComponent: FormatWithTemplate,
+    }
+
+    DetailedPerson: DetailedPersonComponents {
+        ErrorTypeComponent: UseAnyhowError,
+        ErrorRaiserComponent: RaiseIntoAnyhow,
+        StringFormatterComponent: FormatWithTemplate,
+    }
+
+    MisspelledPerson: MisspelledPersonComponents {
+        ErrorTypeComponent: UseAnyhowError,
+        ErrorRaiserComponent: RaiseIntoAnyhow,
+        StringFormatterComponent: FormatWithTemplate,
+    }
+
+    UnclosedPerson: UnclosedPersonComponents {
+        ErrorTypeComponent: UseAnyhowError,
+        ErrorRaiserComponent: RaiseIntoAnyhow,
+        StringFormatterComponent: FormatWithTemplate,
+    }
+}
+
+impl FormatTemplateGetter<Person> for PersonComponents {
+    fn format_template() -> &'static str {
+        "{last_name}, {first_name}"
+    }
+}
+
+impl FormatTemplateGetter<DetailedPerson> for DetailedPersonComponents {
+    fn format_template() -> &'static str {
+        "{{{ first_name }}} ({age}) from {address.city}, aka '{nickname}'"
+    }
+}
+
+impl FormatTemplateGetter<MisspelledPerson> for MisspelledPersonComponents {
+    fn format_template() -> &'static str {
+        "{last_name}, {frist_name}"
+    }
+}
+
+impl FormatTemplateGetter<UnclosedPerson> for UnclosedPersonComponents {
+    fn format_template() -> &'static str {
+        "{last_name"
+    }
+}
+
+#[test]
+fn test_format_with_template() {
+    assert_eq!(Person::new().format_to_string().unwrap(), "Smith, John");
+    assert_eq!(
+        DetailedPerson::new().format_to_string().unwrap(),
+        "{John} (42) from Springfield, aka ''"
+    );
+}
+
+#[test]
+fn test_missing_field_is_raised() {
+    let err = MisspelledPerson::new().format_to_string().unwrap_err();
+
+    let err = err.downcast_ref::<ErrMissingTemplateField>().unwrap();
+    assert_eq!(err.field, "frist_name");
+    assert_eq!(
+        err.to_string(),
+        "missing field `frist_name` used in format template `{last_name}, {frist_name}`"
+    );
+}
+
+#[test]
+fn test_invalid_template_is_raised() {
+    let err = UnclosedPerson::new().format_to_string().unwrap_err();
+
+    let err = err.downcast_ref::<ErrInvalidTemplate>().unwrap();
+    assert_eq!(err.offset, 0);
+}
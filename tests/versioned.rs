@@ -0,0 +1,108 @@
+use cgp::core::error::{ErrorRaiserComponent, ErrorTypeComponent};
+use cgp::prelude::*;
+use cgp_examples::error::{RaiseIntoAnyhow, UseAnyhowError};
+use cgp_examples::format::*;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+// Version 3 of the persisted Person shape.
+//
+// Version 1: {"name":"John Smith"}
+// Version 2: {"first_name":"John","surname":"Smith"}
+// Version 3: {"first_name":"John","last_name":"Smith"}
+#[derive(Serialize, Deserialize, Debug, Eq, PartialEq)]
+pub struct Person {
+    pub first_name: String,
+    pub last_name: String,
+}
+
+pub struct PersonComponents;
+
+impl HasComponents for Person {
+    type Components = PersonComponents;
+}
+
+delegate_components! {
+    PersonComponents {
+        ErrorTypeComponent: UseAnyhowError,
+        ErrorRaiserComponent: RaiseIntoAnyhow,
+        StringFormatterComponent: FormatAsVersionedJson,
+        StringParserComponent: ParseFromVersionedJson,
+        JsonMigratorComponent: MigrationChain<Product![SplitName, RenameSurname]>,
+    }
+}
+
+impl SchemaVersionGetter<Person> for PersonComponents {
+    fn schema_version() -> u64 {
+        3
+    }
+}
+
+pub struct SplitName;
+
+impl JsonMigration<Person> for SplitName {
+    const FROM_VERSION: u64 = 1;
+
+    fn migrate(value: Value) -> Result<Value, anyhow::Error> {
+        let name = value["name"].as_str().unwrap_or_default();
+        let (first_name, surname) = name.split_once(' ').unwrap_or((name, ""));
+
+        Ok(json!({ "first_name": first_name, "surname": surname }))
+    }
+}
+
+pub struct RenameSurname;
+
+impl JsonMigration<Person> for RenameSurname {
+    const FROM_VERSION: u64 = 2;
+
+    fn migrate(mut value: Value) -> Result<Value, anyhow::Error> {
+        if let Some(map) = value.as_object_mut() {
+            if let Some(surname) = map.remove("surname") {
+                map.insert("last_name".into(), surname);
+            }
+        }
+
+        Ok(value)
+    }
+}
+
+fn person() -> Person {
+    Person {
+        first_name: "John".into(),
+        last_name: "Smith".into(),
+    }
+}
+
+#[test]
+fn test_versioned_round_trip() {
+    let person_str = person().format_to_string().unwrap();
+
+    assert_eq!(
+        person_str,
+        r#"{"version":3,"data":{"first_name":"John","last_name":"Smith"}}"#
+    );
+    assert_eq!(Person::parse_from_string(&person_str).unwrap(), person());
+}
+
+#[test]
+fn test_old_versions_are_migrated() {
+    let v1 = r#"{"version":1,"data":{"name":"John Smith"}}"#;
+    let v2 = r#"{"version":2,"data":{"first_name":"John","surname":"Smith"}}"#;
+    let unversioned = r#"{"name":"John Smith"}"#;
+
+    assert_eq!(Person::parse_from_string(v1).unwrap(), person());
+    assert_eq!(Person::parse_from_string(v2).unwrap(), person());
+    assert_eq!(Person::parse_from_string(unversioned).unwrap(), person());
+}
+
+#[test]
+fn test_newer_version_is_rejected() {
+    let v4 = r#"{"version":4,"data":{"first_name":"John","last_name":"Smith"}}"#;
+
+    let err = Person::parse_from_string(v4).unwrap_err();
+    let err = err.downcast_ref::<ErrUnsupportedSchemaVersion>().unwrap();
+
+    assert_eq!(err.version, 4);
+    assert_eq!(err.current_version, 3);
+}